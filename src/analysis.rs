@@ -0,0 +1,258 @@
+use std::{collections::VecDeque, fmt::Display};
+
+use maze_robot::controller::{Cell, DIR_ARR};
+
+use crate::text_maze::TextMaze;
+
+/// Facts about a maze that can be learned from its full grid, without sending a robot in.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Analysis {
+    /// Fewest moves needed to get from the start to a finish, if one is reachable.
+    pub shortest_path: Option<usize>,
+    /// Reachable open cells (other than start & finish) with exactly one open neighbour.
+    pub dead_ends: usize,
+    /// Reachable open cells with three or more open neighbours.
+    pub junctions: usize,
+    /// Open areas the robot can never get to from the start.
+    pub unreachable_regions: Vec<Region>,
+    /// True when every open cell is reachable & there is exactly one route between any two.
+    pub perfect: bool,
+}
+
+/// A connected group of open cells, identified by its first cell in reading order.
+#[derive(Debug, Eq, PartialEq)]
+pub struct Region {
+    pub first: (usize, usize),
+    pub size: usize,
+}
+
+impl Analysis {
+    pub fn finish_reachable(&self) -> bool {
+        self.shortest_path.is_some()
+    }
+}
+
+/// Walk the whole grid of the given maze & collect an `Analysis` of it.
+pub fn analyze(maze: &TextMaze) -> Analysis {
+    let start = maze.start();
+    let mut seen = Visited::new(maze.width(), maze.height());
+    let (mut dead_ends, mut junctions, mut edges) = (0, 0, 0);
+    let (reachable, shortest_path) = flood(maze, start, &mut seen, |posn| {
+        let degree = open_neighbours(maze, posn).count();
+        let is_dead_end = degree == 1 && posn != start && maze.cell_at(posn) != Cell::Finish;
+
+        dead_ends += usize::from(is_dead_end);
+        junctions += usize::from(degree >= 3);
+        edges += degree;
+    });
+
+    let unreachable_regions: Vec<Region> = (0..maze.height())
+        .flat_map(|row| (0..maze.width()).map(move |col| (col, row)))
+        .filter_map(|posn| {
+            if maze.cell_at(posn) == Cell::Wall || seen.contains(posn) {
+                return None;
+            }
+            let (size, _) = flood(maze, posn, &mut seen, |_| ());
+
+            Some(Region { first: posn, size })
+        })
+        .collect();
+
+    // every edge was counted once from each end, so a tree has exactly 2 * (cells - 1)
+    let perfect = unreachable_regions.is_empty() && edges == 2 * (reachable - 1);
+
+    Analysis {
+        shortest_path,
+        dead_ends,
+        junctions,
+        unreachable_regions,
+        perfect,
+    }
+}
+
+/// Breadth first search from the given position, marking off & visiting every open cell reached
+/// that wasn't already seen. Returns how many were reached & the distance to the nearest finish,
+/// if any.
+fn flood(
+    maze: &TextMaze,
+    from: (usize, usize),
+    seen: &mut Visited,
+    mut visit: impl FnMut((usize, usize)),
+) -> (usize, Option<usize>) {
+    seen.insert(from);
+    let mut queue = VecDeque::from([(from, 0)]);
+    let mut reached = 0;
+    let mut nearest_finish = None;
+
+    while let Some((posn, dist)) = queue.pop_front() {
+        reached += 1;
+        visit(posn);
+        if nearest_finish.is_none() && maze.cell_at(posn) == Cell::Finish {
+            nearest_finish = Some(dist);
+        }
+
+        for next in open_neighbours(maze, posn) {
+            if seen.insert(next) {
+                queue.push_back((next, dist + 1));
+            }
+        }
+    }
+
+    (reached, nearest_finish)
+}
+
+/// One bit per cell of the grid, so huge mazes can be searched w/out a set of positions many
+/// times the size of the packed maze.
+struct Visited {
+    bits: Vec<u64>,
+    width: usize,
+}
+
+impl Visited {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            bits: vec![0; (width * height).div_ceil(u64::BITS as usize)],
+            width,
+        }
+    }
+
+    fn locate(&self, (col, row): (usize, usize)) -> (usize, u64) {
+        let idx = row * self.width + col;
+
+        (idx / u64::BITS as usize, 1 << (idx % u64::BITS as usize))
+    }
+
+    fn contains(&self, posn: (usize, usize)) -> bool {
+        let (word, bit) = self.locate(posn);
+
+        self.bits[word] & bit != 0
+    }
+
+    /// Mark the cell as seen, returning true if it wasn't already.
+    fn insert(&mut self, posn: (usize, usize)) -> bool {
+        let (word, bit) = self.locate(posn);
+        let fresh = self.bits[word] & bit == 0;
+        self.bits[word] |= bit;
+
+        fresh
+    }
+}
+
+fn open_neighbours(
+    maze: &TextMaze,
+    posn: (usize, usize),
+) -> impl Iterator<Item = (usize, usize)> + '_ {
+    DIR_ARR
+        .iter()
+        .filter_map(move |&dir| maze.step(posn, dir))
+        .filter(|&next| maze.cell_at(next) != Cell::Wall)
+}
+
+impl Display for Analysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let yes_no = |b: bool| if b { "yes" } else { "no" };
+
+        writeln!(
+            f,
+            "finish reachable:    {}",
+            yes_no(self.finish_reachable())
+        )?;
+        match self.shortest_path {
+            Some(len) => writeln!(f, "shortest path:       {len} moves")?,
+            None => writeln!(f, "shortest path:       none")?,
+        }
+        writeln!(f, "dead ends:           {}", self.dead_ends)?;
+        writeln!(f, "junctions:           {}", self.junctions)?;
+        writeln!(f, "unreachable regions: {}", self.unreachable_regions.len())?;
+        for Region {
+            first: (col, row),
+            size,
+        } in &self.unreachable_regions
+        {
+            writeln!(f, "  - {size} cell(s) starting at column {col}, row {row}")?;
+        }
+        write!(f, "perfect:             {}", yes_no(self.perfect))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    // loop around the centre wall, so not perfect
+    const LOOP_MAZE: &str = "\
++++++
+S   +
++ + +
++   F
++++++";
+
+    const SEALED_MAZE: &str = "\
++++++
+S + F
++++++";
+
+    const WALLED_OFF_MAZE: &str = "\
++++++++
+S    F+
++++++++
++  +  +
++++++++";
+
+    fn analyze_str(maze: &str) -> Analysis {
+        analyze(&TextMaze::try_from(maze).expect("maze to create successfully"))
+    }
+
+    #[rstest]
+    #[case("SF", Some(1))]
+    #[case("S +\n+ F", Some(3))]
+    #[case(LOOP_MAZE, Some(6))]
+    #[case(SEALED_MAZE, None)]
+    fn test_shortest_path(#[case] maze: &str, #[case] exp: Option<usize>) {
+        let act = analyze_str(maze);
+
+        assert_eq!(act.shortest_path, exp);
+        assert_eq!(act.finish_reachable(), exp.is_some());
+    }
+
+    #[rstest]
+    fn test_counts_dead_ends_and_junctions() {
+        //  S  F
+        //  +  +
+        //     +   <- branches off west (dead end) & continues south (dead end)
+        //  +  +
+        let act = analyze_str("S F\n+ +\n  +\n+ +");
+
+        assert_eq!(act.dead_ends, 2);
+        assert_eq!(act.junctions, 2);
+    }
+
+    #[rstest]
+    #[case("S +\n+ F", true)]
+    #[case(LOOP_MAZE, false)]
+    #[case(WALLED_OFF_MAZE, false)]
+    fn test_perfect(#[case] maze: &str, #[case] exp: bool) {
+        assert_eq!(analyze_str(maze).perfect, exp)
+    }
+
+    #[rstest]
+    fn test_unreachable_regions() {
+        let act = analyze_str(WALLED_OFF_MAZE);
+
+        assert_eq!(
+            act.unreachable_regions,
+            vec![
+                Region {
+                    first: (1, 3),
+                    size: 2
+                },
+                Region {
+                    first: (4, 3),
+                    size: 2
+                },
+            ]
+        )
+    }
+}
@@ -0,0 +1,129 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::{Debug, Display},
+    io::Write,
+    thread::sleep,
+    time::Duration,
+};
+
+use maze_robot::controller::{Cell, Direction, Maze, MazeError};
+
+use crate::{
+    render::{EXPLORED, ROBOT, Theme, render_marked},
+    text_maze::TextMaze,
+};
+
+/// Move the cursor to the top left of the terminal & clear everything below it.
+const CLEAR: &str = "\x1b[H\x1b[J";
+
+/// A `TextMaze` that redraws itself to a terminal every time the robot moves.
+///
+/// Each frame shows the robot as 'X' & every cell it has stood in so far as ':', then waits for
+/// `delay` so the search can be followed by eye. The first frame is drawn straight away, showing
+/// the robot at the start.
+#[derive(Debug)]
+pub struct AnimatedMaze<W: Write + Debug> {
+    maze: TextMaze,
+    out: W,
+    delay: Duration,
+    theme: Theme,
+    visited: HashSet<(usize, usize)>,
+}
+
+impl<W: Write + Debug> AnimatedMaze<W> {
+    pub fn new(maze: TextMaze, out: W, delay: Duration, theme: Theme) -> Self {
+        let visited = HashSet::from([maze.position()]);
+        let mut animated = Self {
+            maze,
+            out,
+            delay,
+            theme,
+            visited,
+        };
+
+        animated.show();
+        animated
+    }
+
+    fn frame(&self) -> String {
+        let mut marks: HashMap<(usize, usize), char> =
+            self.visited.iter().map(|&posn| (posn, EXPLORED)).collect();
+        marks.insert(self.maze.position(), ROBOT);
+
+        render_marked(&self.maze, &marks, self.theme)
+    }
+
+    fn draw(&mut self) -> std::io::Result<()> {
+        let frame = self.frame();
+
+        writeln!(self.out, "{CLEAR}{frame}")?;
+        self.out.flush()
+    }
+
+    /// Draw a frame & give it time to be seen.
+    fn show(&mut self) {
+        // a frame that fails to draw shouldn't stop the robot
+        let _ = self.draw();
+        sleep(self.delay);
+    }
+}
+
+impl<W: Write + Debug> Maze for AnimatedMaze<W> {
+    fn look_dir(&self, direction: Direction) -> Cell {
+        self.maze.look_dir(direction)
+    }
+
+    fn move_dir(&mut self, direction: Direction) -> Result<(), MazeError> {
+        self.maze.move_dir(direction)?;
+        self.visited.insert(self.maze.position());
+        self.show();
+
+        Ok(())
+    }
+
+    fn finish_offsets(&self) -> Option<Vec<(isize, isize)>> {
+        self.maze.finish_offsets()
+    }
+}
+
+impl<W: Write + Debug> Display for AnimatedMaze<W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.maze, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    fn animated(maze: &str) -> AnimatedMaze<Vec<u8>> {
+        let maze = TextMaze::try_from(maze).expect("maze to create successfully");
+
+        AnimatedMaze::new(maze, vec![], Duration::ZERO, Theme::default())
+    }
+
+    #[rstest]
+    fn test_draws_frame_per_move() {
+        let mut maze = animated("S  \n+ F");
+
+        maze.move_dir(Direction::East).expect("move to succeed");
+        maze.move_dir(Direction::East).expect("move to succeed");
+        let act = String::from_utf8(maze.out).expect("frames to be utf8");
+
+        assert_eq!(
+            act,
+            format!("{CLEAR}S  \n+ F\n{CLEAR}SX \n+ F\n{CLEAR}S:X\n+ F\n")
+        )
+    }
+
+    #[rstest]
+    fn test_failed_move_draws_nothing() {
+        let mut maze = animated("S+");
+        let start = maze.out.len();
+
+        assert!(maze.move_dir(Direction::East).is_err());
+        assert_eq!(maze.out.len(), start)
+    }
+}
@@ -18,24 +18,172 @@ pub trait Maze: Debug + Display {
     ///
     /// If not possible, a `MazeError::MoveError` will be returned.
     fn move_dir(&mut self, direction: Direction) -> Result<(), MazeError>;
+
+    /// Where the finish cells are, as (cells east, cells north) of the robot, for mazes that
+    /// share this up front like micromouse does w/ its centre goal.
+    ///
+    /// Mazes keep it to themselves unless they say otherwise.
+    fn finish_offsets(&self) -> Option<Vec<(isize, isize)>> {
+        None
+    }
+}
+
+/// Lets a maze picked at runtime be wrapped or placed anywhere a maze is.
+impl<M: Maze + ?Sized> Maze for Box<M> {
+    fn look_dir(&self, direction: Direction) -> Cell {
+        (**self).look_dir(direction)
+    }
+
+    fn move_dir(&mut self, direction: Direction) -> Result<(), MazeError> {
+        (**self).move_dir(direction)
+    }
+
+    fn finish_offsets(&self) -> Option<Vec<(isize, isize)>> {
+        (**self).finish_offsets()
+    }
 }
 
+/// A maze that takes its time to answer, such as a remote simulator or a slow actuator, so a robot
+/// waiting on it can let other robots get on in the meantime.
+///
+/// Any `Maze` can be used as one by wrapping it in `Blocking`.
+pub trait AsyncMaze: Debug + Display {
+    /// Look in the given direction & tell the caller what type of Cell was seen, once the maze
+    /// answers.
+    fn look_dir(&self, direction: Direction) -> impl Future<Output = Cell>;
+
+    /// Attempt to move in the given direction.
+    ///
+    /// If not possible, a `MazeError::MoveError` will be returned.
+    fn move_dir(&mut self, direction: Direction) -> impl Future<Output = Result<(), MazeError>>;
+
+    /// Where the finish cells are relative to the robot, like `Maze::finish_offsets`.
+    fn finish_offsets(&self) -> impl Future<Output = Option<Vec<(isize, isize)>>> {
+        async { None }
+    }
+}
+
+/// A maze that answers straight away, used where an `AsyncMaze` is expected.
+///
+/// Each answer is ready as soon as it is asked for, so nothing else runs while the maze works one
+/// out.
 #[derive(Debug)]
+pub struct Blocking<M: Maze>(pub M);
+
+impl<M: Maze> AsyncMaze for Blocking<M> {
+    async fn look_dir(&self, direction: Direction) -> Cell {
+        self.0.look_dir(direction)
+    }
+
+    async fn move_dir(&mut self, direction: Direction) -> Result<(), MazeError> {
+        self.0.move_dir(direction)
+    }
+
+    async fn finish_offsets(&self) -> Option<Vec<(isize, isize)>> {
+        self.0.finish_offsets()
+    }
+}
+
+impl<M: Maze> Display for Blocking<M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// Where something is in the text a maze was read from, both counted from 1.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SourceLocation {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Display for SourceLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[derive(Debug, PartialEq)]
 pub enum MazeError {
+    /// The maze couldn't be built, for a reason w/out a variant of its own
     CreationError(String),
-    MoveError(Direction, String),
+    /// There was nothing to build a maze from
+    EmptyInput,
+    /// No start cell was marked
+    MissingStart,
+    /// More than one start cell was marked
+    MultipleStarts {
+        first: SourceLocation,
+        second: SourceLocation,
+    },
+    /// A line's length differs from the first line's, w/ `at` the first column that doesn't fit
+    RaggedLine {
+        at: SourceLocation,
+        expected_width: usize,
+    },
+    /// More than one problem was found, in the order they were found
+    Multiple(Vec<MazeError>),
+    /// The robot tried to move into something it can't enter
+    MoveError {
+        direction: Direction,
+        /// Grid position (column, row) the robot was in, counted from the top left
+        position: (usize, usize),
+        blocked_by: Cell,
+        /// Drawing of the maze around the robot
+        surroundings: String,
+    },
+    /// A maze hosted elsewhere couldn't be reached, or answered something unexpected
+    Remote(String),
+    /// A replayed robot asked about something its trace never saw
+    Diverged(String),
+}
+
+impl MazeError {
+    /// A maze that couldn't be built from a file in some format, saying which format it was.
+    pub fn creation(format: &str, msg: &str) -> Self {
+        Self::CreationError(format!("{format}: {msg}"))
+    }
+
+    /// Where in the maze's text the problem is, if it points at a single spot.
+    pub fn location(&self) -> Option<SourceLocation> {
+        match self {
+            Self::MultipleStarts { second, .. } => Some(*second),
+            Self::RaggedLine { at, .. } => Some(*at),
+            _ => None,
+        }
+    }
 }
 
 impl Display for MazeError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let out = match self {
-            Self::CreationError(msg) => format!("CreationError: {msg}"),
-            Self::MoveError(direction, state) => {
-                format!("MoveError: unable to go {direction} from current location:\n\n{state}\n")
+        match self {
+            Self::CreationError(msg) => write!(f, "unable to create maze: {msg}"),
+            Self::EmptyInput => write!(f, "maze is empty"),
+            Self::MissingStart => write!(f, "maze must mark its start w/ 'S'"),
+            Self::MultipleStarts { first, second } => {
+                write!(f, "maze has more than one start, at {first} & {second}")
             }
-        };
-
-        write!(f, "MazeError:{out}")
+            Self::Multiple(errors) => {
+                write!(f, "found {} problems", errors.len())?;
+                errors.iter().try_for_each(|error| write!(f, "\n- {error}"))
+            }
+            Self::RaggedLine { at, expected_width } => write!(
+                f,
+                "line {} is not {expected_width} cells wide like the first line",
+                at.line
+            ),
+            Self::MoveError {
+                direction,
+                position: (col, row),
+                blocked_by,
+                surroundings,
+            } => write!(
+                f,
+                "unable to go {direction} from ({col},{row}), blocked by {blocked_by:?}:\n\n{surroundings}\n"
+            ),
+            Self::Remote(msg) => write!(f, "remote maze failed: {msg}"),
+            Self::Diverged(msg) => write!(f, "replay diverged from its trace: {msg}"),
+        }
     }
 }
 
@@ -1,10 +1,12 @@
 use std::fmt::Display;
 
+use serde::{Deserialize, Serialize};
+
 mod maze;
 mod robot;
 
-pub use maze::{Maze, MazeError};
-pub use robot::{Robot, RobotInternal};
+pub use maze::{AsyncMaze, Blocking, Maze, MazeError, SourceLocation};
+pub use robot::{AsyncRobot, AsyncRobotInternal, Robot, RobotInternal};
 
 pub const DIR_ARR: [Direction; 4] = [
     Direction::North,
@@ -13,7 +15,7 @@ pub const DIR_ARR: [Direction; 4] = [
     Direction::West,
 ];
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub enum Direction {
     North,
     East,
@@ -30,6 +32,19 @@ impl Direction {
             Self::West => Self::East,
         }
     }
+
+    pub fn left(&self) -> Self {
+        match self {
+            Self::North => Self::West,
+            Self::West => Self::South,
+            Self::South => Self::East,
+            Self::East => Self::North,
+        }
+    }
+
+    pub fn right(&self) -> Self {
+        self.left().reverse()
+    }
 }
 
 impl Display for Direction {
@@ -45,7 +60,7 @@ impl Display for Direction {
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum Cell {
     Finish,
     Open,
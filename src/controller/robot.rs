@@ -1,6 +1,8 @@
 use std::{cell::RefCell, fmt::Display};
 
-use super::{Cell, DIR_ARR, Direction, Maze, maze::MazeError};
+use futures::lock::Mutex;
+
+use super::{AsyncMaze, Cell, DIR_ARR, Direction, Maze, maze::MazeError};
 
 pub trait Robot {
     fn get_internal(&self) -> &RobotInternal;
@@ -18,6 +20,51 @@ pub trait Robot {
     }
 }
 
+/// Lets a robot picked at runtime, such as from a command line flag, be used anywhere a robot is.
+impl<R: Robot + ?Sized> Robot for Box<R> {
+    fn get_internal(&self) -> &RobotInternal {
+        (**self).get_internal()
+    }
+
+    fn peek(&self, direction: Direction) -> Cell {
+        (**self).peek(direction)
+    }
+
+    fn peek_all(&self) -> [(Cell, Direction); 4] {
+        (**self).peek_all()
+    }
+
+    fn go(&self, direction: Direction) -> Result<(), MazeError> {
+        (**self).go(direction)
+    }
+}
+
+/// Lets a robot be lent to a solver & still be inspected once the solver is done w/ it.
+impl<R: Robot + ?Sized> Robot for &R {
+    fn get_internal(&self) -> &RobotInternal {
+        (**self).get_internal()
+    }
+
+    fn peek(&self, direction: Direction) -> Cell {
+        (**self).peek(direction)
+    }
+
+    fn peek_all(&self) -> [(Cell, Direction); 4] {
+        (**self).peek_all()
+    }
+
+    fn go(&self, direction: Direction) -> Result<(), MazeError> {
+        (**self).go(direction)
+    }
+}
+
+/// A robot w/ nothing of its own beyond the maze it is in, for mazes w/out a dedicated robot.
+impl Robot for RobotInternal {
+    fn get_internal(&self) -> &RobotInternal {
+        self
+    }
+}
+
 #[derive(Debug)]
 pub struct RobotInternal {
     // maze is actually an _external_ enviroment the robot exists _inside_ of
@@ -49,14 +96,12 @@ impl RobotInternal {
     }
 
     pub fn go(&self, direction: Direction) -> Result<(), MazeError> {
-        #[cfg(test)]
-        {
-            println!("[Robot::go] BEGIN go {direction} from {self}");
-        }
-        self.env
-            .borrow_mut()
-            .move_dir(direction)
-            .map_err(|e| e.into())
+        self.env.borrow_mut().move_dir(direction)
+    }
+
+    /// Where the finish cells are relative to the robot, if its maze says.
+    pub fn finish_offsets(&self) -> Option<Vec<(isize, isize)>> {
+        self.env.borrow().finish_offsets()
     }
 }
 
@@ -67,3 +112,77 @@ impl Display for RobotInternal {
         write!(f, "Robot state:\n{state}")
     }
 }
+
+/// A robot in an `AsyncMaze`, which waits on its maze to answer rather than blocking on it.
+pub trait AsyncRobot {
+    fn peek(&self, direction: Direction) -> impl Future<Output = Cell>;
+
+    fn peek_all(&self) -> impl Future<Output = [(Cell, Direction); 4]> {
+        async {
+            let mut cells = DIR_ARR.map(|dir| (Cell::Wall, dir));
+            for (cell, dir) in &mut cells {
+                *cell = self.peek(*dir).await;
+            }
+            cells
+        }
+    }
+
+    fn go(&self, direction: Direction) -> impl Future<Output = Result<(), MazeError>>;
+
+    /// Where the finish cells are relative to the robot, if its maze says.
+    fn finish_offsets(&self) -> impl Future<Output = Option<Vec<(isize, isize)>>>;
+}
+
+/// Lets an async search borrow its robot, so the robot outlives the future driving it & can be
+/// looked at once the search has been awaited.
+impl<R: AsyncRobot + ?Sized> AsyncRobot for &R {
+    fn peek(&self, direction: Direction) -> impl Future<Output = Cell> {
+        (**self).peek(direction)
+    }
+
+    fn peek_all(&self) -> impl Future<Output = [(Cell, Direction); 4]> {
+        (**self).peek_all()
+    }
+
+    fn go(&self, direction: Direction) -> impl Future<Output = Result<(), MazeError>> {
+        (**self).go(direction)
+    }
+
+    fn finish_offsets(&self) -> impl Future<Output = Option<Vec<(isize, isize)>>> {
+        (**self).finish_offsets()
+    }
+}
+
+/// A robot w/ nothing of its own beyond the `AsyncMaze` it is in.
+impl<M: AsyncMaze> AsyncRobot for AsyncRobotInternal<M> {
+    async fn peek(&self, direction: Direction) -> Cell {
+        self.env.lock().await.look_dir(direction).await
+    }
+
+    async fn go(&self, direction: Direction) -> Result<(), MazeError> {
+        self.env.lock().await.move_dir(direction).await
+    }
+
+    async fn finish_offsets(&self) -> Option<Vec<(isize, isize)>> {
+        self.env.lock().await.finish_offsets().await
+    }
+}
+
+#[derive(Debug)]
+pub struct AsyncRobotInternal<M: AsyncMaze> {
+    // like `RobotInternal`, the maze sits behind interior mutability so the robot can move w/out
+    // being mutable itself. the lock is one that can be held while the maze is waited on, where a
+    // RefCell would be left borrowed across the wait.
+    //
+    // the maze's type is kept rather than boxed, as a maze w/ async methods can't be made into a
+    // trait object.
+    env: Mutex<M>,
+}
+
+impl<M: AsyncMaze> AsyncRobotInternal<M> {
+    pub fn new(maze: M) -> Self {
+        Self {
+            env: Mutex::new(maze),
+        }
+    }
+}
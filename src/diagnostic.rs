@@ -0,0 +1,138 @@
+use std::{error::Error, fmt::Display};
+
+use maze_robot::controller::{MazeError, SourceLocation};
+
+/// Maze errors shown the way rustc shows compile errors, w/ the offending line of the maze file
+/// & a caret under the column at fault:
+///
+/// ```text
+/// line 3 is not 7 cells wide like the first line
+///  --> maze.txt:3:5
+///   |
+/// 3 | +  +
+///   |     ^
+/// ```
+///
+/// Each of several errors gets its own block, & errors w/out a location are shown on their own.
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub file: String,
+    pub problems: Vec<Problem>,
+}
+
+#[derive(Debug)]
+pub struct Problem {
+    pub error: MazeError,
+    /// Where the error is & the text of its line
+    pub source: Option<(SourceLocation, String)>,
+}
+
+impl Diagnostic {
+    /// Point the errors at their lines in the given text, unless none of them have a location.
+    pub fn new(file: &str, text: &str, error: MazeError) -> Result<Self, MazeError> {
+        let errors = match error {
+            MazeError::Multiple(errors) => errors,
+            error if error.location().is_none() => return Err(error),
+            error => vec![error],
+        };
+        if errors.iter().all(|error| error.location().is_none()) {
+            return Err(MazeError::Multiple(errors));
+        }
+
+        let lines: Vec<&str> = text.lines().collect();
+        let problems = errors
+            .into_iter()
+            .map(|error| Problem {
+                source: error.location().map(|at| {
+                    let line = lines.get(at.line - 1).copied().unwrap_or_default();
+                    (at, String::from(line))
+                }),
+                error,
+            })
+            .collect();
+
+        Ok(Self {
+            file: String::from(file),
+            problems,
+        })
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, Problem { error, source }) in self.problems.iter().enumerate() {
+            // the first "error: " is written by whoever prints the diagnostic
+            if idx > 0 {
+                write!(f, "\n\nerror: ")?;
+            }
+            write!(f, "{error}")?;
+
+            if let Some((SourceLocation { line, column }, text)) = source {
+                let gutter = " ".repeat(line.to_string().len());
+                write!(f, "\n{gutter}--> {}:{line}:{column}", self.file)?;
+                write!(f, "\n{gutter} |")?;
+                write!(f, "\n{line} | {text}")?;
+                write!(f, "\n{gutter} | {:>column$}", "^")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Error for Diagnostic {}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::text_maze::TextMaze;
+
+    #[rstest]
+    fn test_points_at_ragged_line() {
+        let text = "+++++\n+S  F\n+ +\n+++++";
+        let error = TextMaze::try_from(text).expect_err("maze to be ragged");
+        let act = Diagnostic::new("maze.txt", text, error).expect("error to have a location");
+
+        assert_eq!(
+            act.to_string(),
+            "\
+line 3 is not 5 cells wide like the first line
+ --> maze.txt:3:4
+  |
+3 | + +
+  |    ^"
+        );
+    }
+
+    #[rstest]
+    fn test_passes_through_errors_w_out_location() {
+        let act = Diagnostic::new("maze.txt", "  ", MazeError::MissingStart);
+
+        assert!(matches!(act, Err(MazeError::MissingStart)));
+    }
+
+    #[rstest]
+    fn test_shows_every_problem() {
+        let text = "S +\n+\n+ S";
+        let error = TextMaze::try_from(text).expect_err("maze to be invalid");
+        let act = Diagnostic::new("maze.txt", text, error).expect("errors to have locations");
+
+        assert_eq!(
+            act.to_string(),
+            "\
+line 2 is not 3 cells wide like the first line
+ --> maze.txt:2:2
+  |
+2 | +
+  |  ^
+
+error: maze has more than one start, at 1:1 & 3:3
+ --> maze.txt:3:3
+  |
+3 | + S
+  |   ^"
+        );
+    }
+}
@@ -0,0 +1,535 @@
+use std::fmt::Display;
+
+use maze_robot::controller::{Cell, DIR_ARR, Direction, Maze, MazeError, SourceLocation};
+
+use crate::finish::{Finish, KnownFinish};
+
+/// A maze w/ thin walls on the edges between cells, drawn as ASCII art like:
+///
+/// ```text
+/// +--+--+--+
+/// |S    |  |
+/// +  +--+  +
+/// |     |F |
+/// +--+--+--+
+/// ```
+///
+/// - '+' (or 'o', or a box-drawing corner) marks the posts at cell corners, every other line is a
+///   row of posts
+/// - '-' between posts is a wall on the north or south edge of a cell
+/// - '|' at a post's column is a wall on the east or west edge of a cell
+/// - 'S' & 'F' inside a cell mark the start & finish
+///
+/// Walls are kept as a bitmask per cell, so a cell can be open on one side & walled on another.
+/// Anything past the edge of the grid counts as a wall.
+#[derive(Debug)]
+pub struct EdgeMaze {
+    walls: Vec<u8>,
+    finish: Finish,
+    width: usize,
+    height: usize,
+    start: usize,
+    loc: usize,
+    cell_width: usize,
+}
+
+/// Box-drawing characters that can stand for a post.
+const BOX_POSTS: [char; 9] = ['┌', '┬', '┐', '├', '┼', '┤', '└', '┴', '┘'];
+
+/// Columns of the posts drawn on a line.
+fn post_columns(line: &[char]) -> Vec<usize> {
+    line.iter()
+        .enumerate()
+        .filter(|(_, chr)| matches!(chr, '+' | 'o') || BOX_POSTS.contains(chr))
+        .map(|(col, _)| col)
+        .collect()
+}
+
+/// First column where a line's posts stop lining up w/ the posts expected, if they ever do.
+fn misplaced_post(expected: &[usize], line: &[char]) -> Option<usize> {
+    let found = post_columns(line);
+
+    (0..expected.len().max(found.len())).find_map(|i| match (expected.get(i), found.get(i)) {
+        (Some(exp), Some(act)) if exp == act => None,
+        (exp, act) => exp.into_iter().chain(act).min().copied(),
+    })
+}
+
+/// Bit set in a cell's walls when there is a wall on the given side of it.
+pub fn wall_bit(direction: Direction) -> u8 {
+    match direction {
+        Direction::North => 1,
+        Direction::East => 2,
+        Direction::South => 4,
+        Direction::West => 8,
+    }
+}
+
+impl EdgeMaze {
+    /// Build a maze from a wall bitmask per cell, in rows from the top left, & the grid positions
+    /// (column, row) of the start & finish cells.
+    ///
+    /// Walls are made consistent, so a wall on one side of an edge is also on the other.
+    pub fn new(
+        width: usize,
+        height: usize,
+        mut walls: Vec<u8>,
+        start: (usize, usize),
+        finish: &[(usize, usize)],
+    ) -> Result<Self, MazeError> {
+        if width == 0 || height == 0 || walls.len() != width * height {
+            return Err(MazeError::CreationError(format!(
+                "EdgeMaze needs exactly one wall mask per cell of its {width}x{height} grid"
+            )));
+        }
+        let in_grid = |&(col, row): &(usize, usize)| col < width && row < height;
+        if !in_grid(&start) || !finish.iter().all(in_grid) {
+            return Err(MazeError::CreationError(String::from(
+                "EdgeMaze start & finish must be inside the grid",
+            )));
+        }
+
+        let mut maze = Self {
+            walls: vec![],
+            finish: Finish::new(finish.to_vec()),
+            width,
+            height,
+            start: start.1 * width + start.0,
+            loc: start.1 * width + start.0,
+            cell_width: 2,
+        };
+        for idx in 0..walls.len() {
+            for dir in DIR_ARR {
+                if walls[idx] & wall_bit(dir) != 0
+                    && let Some(next) = maze.neighbour(idx, dir)
+                {
+                    walls[next] |= wall_bit(dir.reverse());
+                }
+            }
+        }
+        maze.walls = walls;
+
+        Ok(maze)
+    }
+
+    /// True if there is a wall on the given side of the cell at the grid position (column, row).
+    pub fn has_wall(&self, (col, row): (usize, usize), direction: Direction) -> bool {
+        let idx = row * self.width + col;
+
+        self.walls[idx] & wall_bit(direction) != 0 || self.neighbour(idx, direction).is_none()
+    }
+
+    /// Redraw the maze w/ a block for every post & walled edge, so it can be read as a
+    /// `TextMaze`.
+    ///
+    /// Each cell becomes the block at (2 * column + 1, 2 * row + 1), w/ the edges around it
+    /// between cells & the posts at the corners.
+    pub fn to_block_text(&self) -> String {
+        let mut grid = vec![vec!['+'; 2 * self.width + 1]; 2 * self.height + 1];
+
+        for idx in 0..self.walls.len() {
+            let (col, row) = (idx % self.width, idx / self.width);
+            let (x, y) = (2 * col + 1, 2 * row + 1);
+            grid[y][x] = self.marker(idx).unwrap_or(' ');
+            if !self.has_wall((col, row), Direction::East) {
+                grid[y][x + 1] = ' ';
+            }
+            if !self.has_wall((col, row), Direction::South) {
+                grid[y + 1][x] = ' ';
+            }
+        }
+
+        grid.into_iter()
+            .map(|row| row.into_iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Grid position (column, row) of the cell at the given index.
+    fn posn(&self, idx: usize) -> (usize, usize) {
+        (idx % self.width, idx / self.width)
+    }
+
+    fn neighbour(&self, idx: usize, direction: Direction) -> Option<usize> {
+        let (col, row) = self.posn(idx);
+
+        match direction {
+            Direction::North => row.checked_sub(1).map(|r| r * self.width + col),
+            Direction::South => (row + 1 < self.height).then_some(idx + self.width),
+            Direction::East => (col + 1 < self.width).then_some(idx + 1),
+            Direction::West => col.checked_sub(1).map(|_| idx - 1),
+        }
+    }
+
+    fn marker(&self, idx: usize) -> Option<char> {
+        if idx == self.start {
+            Some('S')
+        } else if self.finish.contains(self.posn(idx)) {
+            Some('F')
+        } else {
+            None
+        }
+    }
+
+    fn cell(&self, idx: usize) -> Cell {
+        if self.finish.contains(self.posn(idx)) {
+            Cell::Finish
+        } else {
+            Cell::Open
+        }
+    }
+}
+
+impl Maze for EdgeMaze {
+    fn look_dir(&self, direction: Direction) -> Cell {
+        match self.neighbour(self.loc, direction) {
+            Some(next) if self.walls[self.loc] & wall_bit(direction) == 0 => self.cell(next),
+            _ => Cell::Wall,
+        }
+    }
+
+    fn move_dir(&mut self, direction: Direction) -> Result<(), MazeError> {
+        match self.look_dir(direction) {
+            Cell::Wall => Err(MazeError::MoveError {
+                direction,
+                position: self.posn(self.loc),
+                blocked_by: Cell::Wall,
+                surroundings: self.to_string(),
+            }),
+            _ => {
+                self.loc = self
+                    .neighbour(self.loc, direction)
+                    .expect("open cells to be inside the grid");
+                Ok(())
+            }
+        }
+    }
+
+    fn finish_offsets(&self) -> Option<Vec<(isize, isize)>> {
+        self.finish.offsets_from(self.posn(self.loc))
+    }
+}
+
+impl KnownFinish for EdgeMaze {
+    fn finish_mut(&mut self) -> &mut Finish {
+        &mut self.finish
+    }
+}
+
+/// The walls & markers read from an edge-walled drawing, before a start & finish are settled on.
+#[derive(Debug)]
+pub struct Drawing {
+    pub width: usize,
+    pub height: usize,
+    pub walls: Vec<u8>,
+    /// Cell marked 'S', if any, & where it was marked
+    pub start: Option<((usize, usize), SourceLocation)>,
+    /// Cells marked 'F'
+    pub finish: Vec<(usize, usize)>,
+    cell_width: usize,
+}
+
+impl Drawing {
+    /// Read the walls from `+--+` / `|` art, where posts may be drawn as '+', 'o' or box-drawing
+    /// corners & junctions like '┌' & '┼', w/ walls between them drawn as '─' & '│'.
+    pub fn parse(value: &str) -> Result<Self, MazeError> {
+        if value.lines().all(str::is_empty) {
+            return Err(MazeError::EmptyInput);
+        }
+        let lines: Vec<Vec<char>> = value.lines().map(|line| line.chars().collect()).collect();
+        let posts = lines
+            .first()
+            .map(|line| post_columns(line))
+            .unwrap_or_default();
+        if posts.len() < 2 || lines.len() < 3 || lines.len().is_multiple_of(2) {
+            return Err(MazeError::CreationError(String::from(
+                "EdgeMaze must start & end w/ a line of '+' posts, w/ one line of cells between each",
+            )));
+        }
+        // every line of posts has to line up w/ the first, & nothing can stick out past it
+        let expected_width = lines[0].len();
+        for (line_idx, line) in lines.iter().enumerate() {
+            let misplaced = match line_idx % 2 {
+                0 => misplaced_post(&posts, line),
+                _ => None,
+            };
+            let column = misplaced.or((line.len() > expected_width).then_some(expected_width));
+
+            if let Some(column) = column {
+                return Err(MazeError::RaggedLine {
+                    at: SourceLocation {
+                        line: line_idx + 1,
+                        column: column + 1,
+                    },
+                    expected_width,
+                });
+            }
+        }
+
+        let width = posts.len() - 1;
+        let height = lines.len() / 2;
+        // short lines are treated as if padded w/ spaces, since editors often trim them
+        let at = |line: usize, col: usize| lines[line].get(col).copied().unwrap_or(' ');
+        let mut walls = vec![0; width * height];
+        let mut start = None;
+        let mut finish = vec![];
+
+        for row in 0..height {
+            let (above, middle, below) = (2 * row, 2 * row + 1, 2 * row + 2);
+            for col in 0..width {
+                let (left, right) = (posts[col], posts[col + 1]);
+                let inside = left + 1..right;
+                let mask = &mut walls[row * width + col];
+
+                if inside.clone().any(|c| at(above, c) != ' ') {
+                    *mask |= wall_bit(Direction::North);
+                }
+                if inside.clone().any(|c| at(below, c) != ' ') {
+                    *mask |= wall_bit(Direction::South);
+                }
+                if at(middle, left) != ' ' {
+                    *mask |= wall_bit(Direction::West);
+                }
+                if at(middle, right) != ' ' {
+                    *mask |= wall_bit(Direction::East);
+                }
+
+                for c in inside {
+                    match at(middle, c) {
+                        'S' => {
+                            let at = SourceLocation {
+                                line: middle + 1,
+                                column: c + 1,
+                            };
+                            if let Some((_, first)) = start {
+                                return Err(MazeError::MultipleStarts { first, second: at });
+                            }
+                            start = Some(((col, row), at));
+                        }
+                        'F' => finish.push((col, row)),
+                        _ => (),
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            width,
+            height,
+            walls,
+            start,
+            finish,
+            cell_width: posts[1] - posts[0] - 1,
+        })
+    }
+
+    /// Build the maze w/ the given start & finish, ignoring any marked in the drawing.
+    pub fn into_maze(
+        self,
+        start: (usize, usize),
+        finish: &[(usize, usize)],
+    ) -> Result<EdgeMaze, MazeError> {
+        let mut maze = EdgeMaze::new(self.width, self.height, self.walls, start, finish)?;
+        maze.cell_width = self.cell_width;
+
+        Ok(maze)
+    }
+}
+
+impl TryFrom<&str> for EdgeMaze {
+    type Error = MazeError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let drawing = Drawing::parse(value)?;
+        let (start, _) = drawing.start.ok_or(MazeError::MissingStart)?;
+        let finish = drawing.finish.clone();
+
+        drawing.into_maze(start, &finish)
+    }
+}
+
+impl Display for EdgeMaze {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let span = self.cell_width.max(1);
+        let mut lines = vec![];
+
+        for row in 0..self.height {
+            let top = (0..self.width)
+                .map(|col| edge(self.has_wall((col, row), Direction::North), span))
+                .collect::<Vec<_>>();
+            lines.push(format!("+{}+", top.join("+")));
+
+            let mut middle = String::new();
+            for col in 0..self.width {
+                let idx = row * self.width + col;
+                middle.push(if self.has_wall((col, row), Direction::West) {
+                    '|'
+                } else {
+                    ' '
+                });
+                let mark = if idx == self.loc {
+                    Some('X')
+                } else {
+                    self.marker(idx)
+                };
+                middle.push_str(&match mark {
+                    Some(chr) => format!("{chr:<span$}"),
+                    None => " ".repeat(span),
+                });
+            }
+            middle.push('|');
+            lines.push(middle);
+        }
+
+        let bottom = (0..self.width)
+            .map(|col| {
+                edge(
+                    self.has_wall((col, self.height - 1), Direction::South),
+                    span,
+                )
+            })
+            .collect::<Vec<_>>();
+        lines.push(format!("+{}+", bottom.join("+")));
+
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Horizontal edge between two posts, drawn as dashes if walled.
+fn edge(walled: bool, span: usize) -> String {
+    if walled { "-" } else { " " }.repeat(span)
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    pub const MAZE: &str = "\
++--+--+--+
+|S    |  |
++  +--+  +
+|     |F |
++--+--+--+";
+
+    fn make_maze(maze: &str) -> EdgeMaze {
+        EdgeMaze::try_from(maze).expect("maze to create successfully")
+    }
+
+    #[rstest]
+    #[case(Direction::North, Cell::Wall)]
+    #[case(Direction::East, Cell::Open)]
+    #[case(Direction::South, Cell::Open)]
+    #[case(Direction::West, Cell::Wall)]
+    fn test_look_from_start(#[case] direction: Direction, #[case] exp: Cell) {
+        assert_eq!(make_maze(MAZE).look_dir(direction), exp)
+    }
+
+    #[rstest]
+    fn test_thin_wall_blocks_neighbouring_cells() {
+        let mut maze = make_maze(MAZE);
+        maze.move_dir(Direction::East).expect("move to succeed");
+
+        // the middle cell of the top row has '|' to its east & '--' to its south
+        assert_eq!(maze.look_dir(Direction::East), Cell::Wall);
+        assert_eq!(maze.look_dir(Direction::South), Cell::Wall);
+        assert!(matches!(
+            maze.move_dir(Direction::East),
+            Err(MazeError::MoveError { .. })
+        ));
+    }
+
+    #[rstest]
+    fn test_sees_finish() {
+        let mut maze = make_maze("+--+--+\n|S  F |\n+--+--+");
+
+        assert_eq!(maze.look_dir(Direction::East), Cell::Finish);
+        maze.move_dir(Direction::East).expect("move to succeed");
+    }
+
+    #[rstest]
+    fn test_render() {
+        let mut maze = make_maze(MAZE);
+        maze.move_dir(Direction::South).expect("move to succeed");
+
+        assert_eq!(
+            maze.to_string(),
+            "\
++--+--+--+
+|S    |  |
++  +--+  +
+|X    |F |
++--+--+--+"
+        )
+    }
+
+    #[rstest]
+    fn test_to_block_text() {
+        assert_eq!(
+            make_maze(MAZE).to_block_text(),
+            "\
++++++++
++S  + +
++ +++ +
++   +F+
++++++++"
+        )
+    }
+
+    #[rstest]
+    fn test_trimmed_lines_and_wide_cells() {
+        let maze = make_maze("+---+---+\n|S   F\n+---+---+");
+
+        assert_eq!(maze.to_block_text(), "+++++\n+S F+\n+++++");
+        assert_eq!(maze.look_dir(Direction::East), Cell::Finish);
+        assert!(maze.has_wall((1, 0), Direction::East));
+    }
+
+    #[rstest]
+    #[case::no_posts("S F")]
+    #[case::even_lines("+--+\n|S |")]
+    fn test_invalid(#[case] maze: &str) {
+        match EdgeMaze::try_from(maze) {
+            Err(MazeError::CreationError(_)) => (),
+            other => panic!("expected CreationError, got {other:?}"),
+        }
+    }
+
+    #[rstest]
+    #[case::empty("", MazeError::EmptyInput)]
+    #[case::no_start("+--+\n|  |\n+--+", MazeError::MissingStart)]
+    #[case::two_starts(
+        "+--+--+\n|S |S |\n+--+--+",
+        MazeError::MultipleStarts {
+            first: SourceLocation { line: 2, column: 2 },
+            second: SourceLocation { line: 2, column: 5 },
+        }
+    )]
+    fn test_invalid_markers(#[case] maze: &str, #[case] exp: MazeError) {
+        match EdgeMaze::try_from(maze) {
+            Err(act) => assert_eq!(act, exp),
+            Ok(_) => panic!("expected {exp:?}"),
+        }
+    }
+
+    #[rstest]
+    #[case::short_first_line("+--+\n|S    |\n+--+--+", 2, 5)]
+    #[case::moved_post("+--+--+\n|S    |\n+---+-+", 3, 4)]
+    #[case::missing_post("+--+--+\n|S    |\n+--+", 3, 7)]
+    fn test_ragged_lines(#[case] maze: &str, #[case] line: usize, #[case] column: usize) {
+        match EdgeMaze::try_from(maze) {
+            Err(MazeError::RaggedLine { at, .. }) => {
+                assert_eq!(at, SourceLocation { line, column })
+            }
+            other => panic!("expected RaggedLine, got {other:?}"),
+        }
+    }
+
+    #[rstest]
+    fn test_box_drawing() {
+        let maze = make_maze("┌──┬──┬──┐\n│S    │  │\n├  ┼──┘  ┤\n│     │F │\n└──┴──┴──┘");
+
+        assert_eq!(maze.to_block_text(), make_maze(MAZE).to_block_text());
+    }
+}
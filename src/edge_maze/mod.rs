@@ -0,0 +1,5 @@
+mod maze_impl;
+mod robot_impl;
+
+pub use maze_impl::{Drawing, EdgeMaze};
+pub use robot_impl::EdgeRobot;
@@ -0,0 +1,34 @@
+use std::fmt::Display;
+
+use maze_robot::controller::{MazeError, Robot, RobotInternal};
+
+use crate::edge_maze::EdgeMaze;
+
+#[derive(Debug)]
+pub struct EdgeRobot(RobotInternal);
+
+impl From<EdgeMaze> for EdgeRobot {
+    fn from(value: EdgeMaze) -> Self {
+        EdgeRobot(RobotInternal::new(value))
+    }
+}
+
+impl TryFrom<&str> for EdgeRobot {
+    type Error = MazeError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Ok(EdgeMaze::try_from(value)?.into())
+    }
+}
+
+impl Robot for EdgeRobot {
+    fn get_internal(&self) -> &RobotInternal {
+        &self.0
+    }
+}
+
+impl Display for EdgeRobot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
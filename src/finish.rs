@@ -0,0 +1,43 @@
+/// A maze's finish cells, found once while reading it, & whether the robot is told where they
+/// are.
+#[derive(Debug, Default)]
+pub struct Finish {
+    /// Grid position (column, row) of every finish cell.
+    cells: Vec<(usize, usize)>,
+    known: bool,
+}
+
+impl Finish {
+    pub fn new(cells: Vec<(usize, usize)>) -> Self {
+        Self {
+            cells,
+            known: false,
+        }
+    }
+
+    pub fn contains(&self, posn: (usize, usize)) -> bool {
+        self.cells.contains(&posn)
+    }
+
+    /// Steps (east, north) from the grid position (column, row) to each finish cell, if the robot
+    /// is told where they are.
+    pub fn offsets_from(&self, (col, row): (usize, usize)) -> Option<Vec<(isize, isize)>> {
+        self.known.then(|| {
+            self.cells
+                .iter()
+                .map(|&(c, r)| (c as isize - col as isize, row as isize - r as isize))
+                .collect()
+        })
+    }
+}
+
+/// A maze that keeps where its finish is to itself unless asked to share it.
+pub trait KnownFinish: Sized {
+    fn finish_mut(&mut self) -> &mut Finish;
+
+    /// Tell the robot where the finish cells are, for solvers that head straight for them.
+    fn with_known_finish(mut self) -> Self {
+        self.finish_mut().known = true;
+        self
+    }
+}
@@ -0,0 +1,99 @@
+/// Build a perfect maze of `width` x `height` rooms in the `TextMaze` format.
+///
+/// Rooms sit on odd rows & columns of the text, w/ walls between them knocked out by a randomised
+/// depth first walk (recursive backtracker). The start is cut into the west wall of the top-left
+/// room & the finish into the east wall of the bottom-right room. The same seed always gives the
+/// same maze.
+pub fn generate(width: usize, height: usize, seed: u64) -> String {
+    let cols = 2 * width + 1;
+    let rows = 2 * height + 1;
+    let mut grid = vec![vec!['+'; cols]; rows];
+    let mut rng = Rng::new(seed);
+
+    let mut visited = vec![vec![false; width]; height];
+    let mut stack: Vec<(usize, usize)> = vec![(0, 0)];
+    visited[0][0] = true;
+    grid[1][1] = ' ';
+
+    while let Some(&(x, y)) = stack.last() {
+        let unvisited: Vec<(usize, usize)> = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ]
+        .into_iter()
+        .filter(|&(nx, ny)| nx < width && ny < height && !visited[ny][nx])
+        .collect();
+
+        if unvisited.is_empty() {
+            stack.pop();
+            continue;
+        }
+
+        let (nx, ny) = unvisited[rng.below(unvisited.len())];
+        visited[ny][nx] = true;
+        // open the room itself & the wall between it & the current room
+        grid[2 * ny + 1][2 * nx + 1] = ' ';
+        grid[y + ny + 1][x + nx + 1] = ' ';
+        stack.push((nx, ny));
+    }
+
+    grid[1][0] = 'S';
+    grid[rows - 2][cols - 1] = 'F';
+
+    grid.into_iter()
+        .map(|row| row.into_iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Small xorshift64* generator, so mazes are reproducible from a seed w/out extra dependencies.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // xorshift gets stuck on a zero state, so mix the seed into something non-zero first
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{analysis::analyze, text_maze::TextMaze};
+
+    #[rstest]
+    #[case(1, 1)]
+    #[case(8, 5)]
+    #[case(20, 20)]
+    fn test_generates_perfect_mazes(#[case] width: usize, #[case] height: usize) {
+        let text = generate(width, height, 42);
+        let maze = TextMaze::try_from(text.as_str()).expect("maze to create successfully");
+        let report = analyze(&maze);
+
+        assert_eq!(maze.width(), 2 * width + 1);
+        assert_eq!(maze.height(), 2 * height + 1);
+        assert!(report.finish_reachable());
+        assert!(report.perfect);
+    }
+
+    #[rstest]
+    fn test_same_seed_same_maze() {
+        assert_eq!(generate(10, 10, 7), generate(10, 10, 7));
+        assert_ne!(generate(10, 10, 7), generate(10, 10, 8));
+    }
+}
@@ -0,0 +1,232 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use maze_robot::controller::{Cell, MazeError};
+
+use crate::{
+    analysis::analyze,
+    render::render_marked,
+    text_maze::{Role, TextMaze},
+};
+
+/// Name given to the format in errors.
+const FORMAT: &str = "JSON maze";
+
+/// A maze described by explicit fields instead of by position in a text file.
+///
+/// Rows are read through the legend, then the `start` & `goals` positions are placed on top, so
+/// rows don't need to mark them. Positions are `[column, row]`, counted from the top left.
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+pub struct JsonMaze {
+    pub dimensions: Dimensions,
+    pub rows: Vec<String>,
+    pub start: [usize; 2],
+    pub goals: Vec<[usize; 2]>,
+    #[serde(default = "default_legend")]
+    pub legend: BTreeMap<char, Role>,
+    #[serde(default)]
+    pub metadata: Metadata,
+}
+
+#[derive(Debug, Deserialize, Serialize, PartialEq)]
+pub struct Dimensions {
+    pub width: usize,
+    pub height: usize,
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, PartialEq)]
+pub struct Metadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Moves in the shortest path from start to finish
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub par: Option<usize>,
+}
+
+/// The characters a `TextMaze` uses.
+fn default_legend() -> BTreeMap<char, Role> {
+    BTreeMap::from([
+        ('+', Role::Wall),
+        (' ', Role::Open),
+        ('S', Role::Start),
+        ('F', Role::Finish),
+    ])
+}
+
+impl JsonMaze {
+    /// Describe an existing maze, w/ its par taken from the shortest path through it.
+    pub fn from_text_maze(maze: &TextMaze, name: Option<String>) -> Self {
+        let goals = (0..maze.height())
+            .flat_map(|row| (0..maze.width()).map(move |col| (col, row)))
+            .filter(|&posn| maze.cell_at(posn) == Cell::Finish)
+            .map(|(col, row)| [col, row])
+            .collect();
+        let (start_col, start_row) = maze.start();
+
+        Self {
+            dimensions: Dimensions {
+                width: maze.width(),
+                height: maze.height(),
+            },
+            rows: render_marked(maze, &Default::default(), Default::default())
+                .lines()
+                .map(String::from)
+                .collect(),
+            start: [start_col, start_row],
+            goals,
+            legend: default_legend(),
+            metadata: Metadata {
+                name,
+                author: None,
+                par: analyze(maze).shortest_path,
+            },
+        }
+    }
+
+    /// Lay the maze out in the text maze format, so it can be loaded as a `TextMaze`.
+    pub fn to_text(&self) -> Result<String, MazeError> {
+        let Dimensions { width, height } = self.dimensions;
+        if self.rows.len() != height {
+            return Err(MazeError::creation(
+                FORMAT,
+                &format!(
+                    "dimensions give {height} rows but {} were found",
+                    self.rows.len()
+                ),
+            ));
+        }
+
+        let mut grid = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(idx, row)| {
+                let cells: Vec<char> = row
+                    .chars()
+                    .map(|chr| match self.legend.get(&chr) {
+                        Some(Role::Wall) => Ok('+'),
+                        // the start field is the only start, so any marked in rows are just open
+                        Some(Role::Open | Role::Start) => Ok(' '),
+                        Some(Role::Finish) => Ok('F'),
+                        None => Err(MazeError::creation(
+                            FORMAT,
+                            &format!("row {idx} uses '{chr}', which is not in the legend"),
+                        )),
+                    })
+                    .collect::<Result<_, _>>()?;
+
+                if cells.len() == width {
+                    Ok(cells)
+                } else {
+                    Err(MazeError::creation(
+                        FORMAT,
+                        &format!(
+                            "row {idx} is {} wide, but dimensions give a width of {width}",
+                            cells.len()
+                        ),
+                    ))
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (&[col, row], chr) in self
+            .goals
+            .iter()
+            .map(|goal| (goal, 'F'))
+            .chain([(&self.start, 'S')])
+        {
+            *grid
+                .get_mut(row)
+                .and_then(|cells| cells.get_mut(col))
+                .ok_or(MazeError::creation(
+                    FORMAT,
+                    &format!("[{col}, {row}] is outside the {width}x{height} maze"),
+                ))? = chr;
+        }
+
+        Ok(grid
+            .into_iter()
+            .map(|row| row.into_iter().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const MAZE: &str = "\
+#####
+@.#.#
+#...E
+#####";
+
+    const TEXT: &str = "\
++++++
+S + +
++   F
++++++";
+
+    fn parse(json: &str) -> JsonMaze {
+        serde_json::from_str(json).expect("json to parse")
+    }
+
+    #[rstest]
+    fn test_to_text_w_legend() {
+        let json = format!(
+            r##"{{
+                "dimensions": {{ "width": 5, "height": 4 }},
+                "rows": {rows},
+                "start": [0, 1],
+                "goals": [[4, 2]],
+                "legend": {{ "#": "wall", ".": "open", "@": "start", "E": "finish" }},
+                "metadata": {{ "name": "tiny", "author": "someone", "par": 5 }}
+            }}"##,
+            rows = serde_json::to_string(&MAZE.lines().collect::<Vec<_>>()).unwrap()
+        );
+        let maze = parse(&json);
+
+        assert_eq!(maze.metadata.name.as_deref(), Some("tiny"));
+        assert_eq!(maze.metadata.par, Some(5));
+        assert_eq!(maze.to_text().expect("maze to convert"), TEXT);
+    }
+
+    #[rstest]
+    fn test_round_trip() {
+        let text_maze = TextMaze::try_from(TEXT).expect("maze to create successfully");
+        let exported = JsonMaze::from_text_maze(&text_maze, Some(String::from("tiny")));
+        let json = serde_json::to_string(&exported).expect("maze to serialize");
+        let imported = parse(&json);
+
+        assert_eq!(imported, exported);
+        assert_eq!(imported.metadata.par, Some(5));
+        assert_eq!(imported.goals, vec![[4, 2]]);
+        assert_eq!(imported.to_text().expect("maze to convert"), TEXT);
+    }
+
+    #[rstest]
+    #[case::short_row(
+        r#"{"dimensions":{"width":2,"height":1},"rows":["S"],"start":[0,0],"goals":[]}"#
+    )]
+    #[case::missing_row(
+        r#"{"dimensions":{"width":1,"height":2},"rows":["S"],"start":[0,0],"goals":[]}"#
+    )]
+    #[case::unknown_char(
+        r#"{"dimensions":{"width":1,"height":1},"rows":["?"],"start":[0,0],"goals":[]}"#
+    )]
+    #[case::goal_outside(
+        r#"{"dimensions":{"width":1,"height":1},"rows":[" "],"start":[0,0],"goals":[[3,0]]}"#
+    )]
+    fn test_to_text_invalid(#[case] json: &str) {
+        match parse(json).to_text() {
+            Err(MazeError::CreationError(_)) => (),
+            other => panic!("expected CreationError, got {other:?}"),
+        }
+    }
+}
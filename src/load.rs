@@ -0,0 +1,199 @@
+use std::{
+    fs::{read, read_to_string},
+    path::Path,
+};
+
+use anyhow::anyhow;
+use clap::{Args, ValueEnum};
+
+use maze_robot::controller::{Maze, MazeError, Robot};
+
+use crate::{
+    diagnostic::Diagnostic,
+    edge_maze::{EdgeMaze, EdgeRobot},
+    finish::KnownFinish,
+    json_maze::JsonMaze,
+    micromouse::{read_maz, read_micromouse_text},
+    netpbm::{FINISH_GREY, Markers, START_GREY, read_netpbm},
+    text_maze::{Legend, ParseMode, TextMaze, TextRobot},
+};
+
+/// Where to read a maze from, & how to make sense of it.
+#[derive(Args)]
+pub struct MazeSource {
+    /// Maze file to read: text, JSON, a PBM/PGM image or a micromouse `.maz` file
+    pub maze_file: String,
+    /// Text mazes only: whether walls fill whole cells or sit on the edges between them
+    #[arg(long, value_enum, default_value_t)]
+    pub walls: Walls,
+    /// Text mazes only: how forgiving to be of ragged lines, tabs & odd line endings
+    #[arg(long, value_enum, default_value_t)]
+    pub parse: ParseMode,
+    /// Text mazes only: characters for each role, like `#=wall .=open @=start E=finish`, unless
+    /// the file starts w/ its own `legend:` line
+    #[arg(long)]
+    pub legend: Option<Legend>,
+    /// Images only: start cell as `col,row`, instead of a pixel at the start grey
+    #[arg(long, value_parser = parse_posn)]
+    pub start: Option<(usize, usize)>,
+    /// Images only: a finish cell as `col,row`, may be given more than once
+    #[arg(long, value_parser = parse_posn)]
+    pub finish: Vec<(usize, usize)>,
+    /// Images only: grey level marking the start cell
+    #[arg(long, default_value_t = START_GREY)]
+    pub start_grey: u16,
+    /// Images only: grey level marking finish cells
+    #[arg(long, default_value_t = FINISH_GREY)]
+    pub finish_grey: u16,
+    /// Tell the robot where the finish is before it sets off, as micromouse mazes always do, for
+    /// solvers like flood-fill that head straight for it
+    #[arg(long)]
+    pub finish_known: bool,
+}
+
+/// How a text maze draws its walls.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum Walls {
+    /// Walls are cells of their own, drawn as '+'
+    #[default]
+    Block,
+    /// Thin walls between cells, drawn as `+--+` & `|` art
+    Edge,
+    /// Thin walls drawn w/ 'o' posts, starting bottom left w/ the 2x2 centre as the goal
+    Micromouse,
+}
+
+impl MazeSource {
+    /// Name of the maze, taken from its file name.
+    pub fn name(&self) -> Option<String> {
+        Path::new(&self.maze_file)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .map(String::from)
+    }
+
+    /// Read the maze file, converting it into the text maze format if it is in another format.
+    ///
+    /// Edge-walled mazes are redrawn w/ block walls, so a path through them is twice as long.
+    pub fn read(&self) -> anyhow::Result<String> {
+        match self.edge_maze()? {
+            Some(maze) => Ok(maze.to_block_text()),
+            None => self.read_file(),
+        }
+    }
+
+    /// Whether the maze's walls sit on the edges between cells, so `read` has to redraw it.
+    pub fn has_edge_walls(&self) -> bool {
+        self.extension().as_deref() == Some("maz")
+            || matches!(self.walls, Walls::Edge | Walls::Micromouse)
+    }
+
+    /// Build a `TextMaze` from text returned by `read`.
+    pub fn parse(&self, text: &str) -> anyhow::Result<TextMaze> {
+        // other formats are converted into text w/ the default characters
+        let legend = match self.extension().as_deref() {
+            Some("pbm" | "pgm" | "json" | "maz") => None,
+            _ => self.legend.as_ref(),
+        };
+
+        let maze = TextMaze::parse(text, self.parse, legend.unwrap_or(&Legend::default()))
+            .map_err(|e| match self.walls {
+                Walls::Block => self.diagnose(text, e),
+                // edge-walled mazes were redrawn, so the text isn't what is in the file
+                Walls::Edge | Walls::Micromouse => e.into(),
+            })?;
+
+        Ok(match self.finish_known {
+            true => maze.with_known_finish(),
+            false => maze,
+        })
+    }
+
+    /// Load the maze as whichever kind of maze suits the file, for wrapping before placing a robot.
+    pub fn maze(&self) -> anyhow::Result<Box<dyn Maze>> {
+        Ok(match self.edge_maze()? {
+            Some(maze) => Box::new(maze),
+            None => Box::new(self.parse(&self.read_file()?)?),
+        })
+    }
+
+    /// Place a robot in the maze, keeping edge-walled mazes' walls on the edges between cells.
+    pub fn robot(&self) -> anyhow::Result<Box<dyn Robot>> {
+        Ok(match self.edge_maze()? {
+            Some(maze) => Box::new(EdgeRobot::from(maze)),
+            None => Box::new(TextRobot::new(self.parse(&self.read_file()?)?)),
+        })
+    }
+
+    /// Point the error at the line of the maze file it came from, if the file is the text that
+    /// was parsed rather than something converted into it.
+    fn diagnose(&self, text: &str, error: MazeError) -> anyhow::Error {
+        match self.extension().as_deref() {
+            Some("pbm" | "pgm" | "json" | "maz") => error.into(),
+            _ => match Diagnostic::new(&self.maze_file, text, error) {
+                Ok(diagnostic) => diagnostic.into(),
+                Err(error) => error.into(),
+            },
+        }
+    }
+
+    /// Load the maze w/ its walls on the edges between cells, if it has them.
+    fn edge_maze(&self) -> anyhow::Result<Option<EdgeMaze>> {
+        if self.extension().as_deref() == Some("maz") {
+            return Ok(Some(read_maz(&read(&self.maze_file)?)?));
+        }
+
+        Ok(match self.walls {
+            Walls::Block => None,
+            Walls::Edge => {
+                let text = self.read_file()?;
+                let maze =
+                    EdgeMaze::try_from(text.as_str()).map_err(|e| self.diagnose(&text, e))?;
+                Some(match self.finish_known {
+                    true => maze.with_known_finish(),
+                    false => maze,
+                })
+            }
+            Walls::Micromouse => {
+                let text = self.read_file()?;
+                Some(read_micromouse_text(&text).map_err(|e| self.diagnose(&text, e))?)
+            }
+        })
+    }
+
+    fn extension(&self) -> Option<String> {
+        Path::new(&self.maze_file)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_ascii_lowercase)
+    }
+
+    fn read_file(&self) -> anyhow::Result<String> {
+        match self.extension().as_deref() {
+            Some("pbm" | "pgm") => {
+                let markers = Markers {
+                    start: self.start,
+                    finish: self.finish.clone(),
+                    start_grey: self.start_grey,
+                    finish_grey: self.finish_grey,
+                };
+
+                Ok(read_netpbm(&read(&self.maze_file)?, &markers)?)
+            }
+            Some("json") => {
+                let maze: JsonMaze = serde_json::from_str(&read_to_string(&self.maze_file)?)?;
+
+                Ok(maze.to_text()?)
+            }
+            _ => Ok(read_to_string(&self.maze_file)?),
+        }
+    }
+}
+
+pub fn parse_posn(value: &str) -> anyhow::Result<(usize, usize)> {
+    let (col, row) = value
+        .split_once(',')
+        .ok_or(anyhow!("expected a position as `col,row`"))?;
+
+    Ok((col.trim().parse()?, row.trim().parse()?))
+}
@@ -1,22 +1,693 @@
-use std::fs::read_to_string;
+use std::{
+    collections::HashMap,
+    env::args_os,
+    ffi::OsString,
+    fs::{File, read_to_string, write},
+    io::{BufRead, BufReader, BufWriter, Read, Write, stderr, stdin, stdout},
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+    process::ExitCode,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+#[cfg(unix)]
+use std::{
+    fs::{remove_file, symlink_metadata},
+    os::unix::{
+        fs::FileTypeExt,
+        net::{UnixListener, UnixStream},
+    },
+    path::Path,
+};
 
-use clap::Parser;
+use anyhow::anyhow;
+use clap::{Args, CommandFactory, Parser, Subcommand};
 
+use futures::{executor::block_on, future::join_all};
+use maze_robot::controller::{AsyncRobotInternal, Blocking, Maze, Robot, RobotInternal};
+
+mod analysis;
+mod animate;
+mod diagnostic;
+mod edge_maze;
+mod finish;
+mod generate;
+mod json_maze;
+mod load;
+mod micromouse;
+mod netpbm;
+mod output;
+mod record;
+mod remote;
+mod render;
 mod solution;
+mod svg;
 mod text_maze;
-use crate::solution::solve;
+use crate::{
+    analysis::analyze,
+    animate::AnimatedMaze,
+    generate::generate,
+    json_maze::JsonMaze,
+    load::{MazeSource, parse_posn},
+    netpbm::{Netpbm, write_netpbm},
+    output::OutputFormat,
+    record::{RecordingMaze, ReplayMaze},
+    remote::{RemoteMaze, serve},
+    render::{PathGlyphs, RenderFormat, Theme, render_marked, render_run},
+    solution::{KnownMap, MappingRobot, Run, Solver, drive, go_home},
+    svg::{Layers, to_svg},
+    text_maze::TextRobot,
+};
 
 #[derive(Parser)]
-#[command(version, about, long_about = None)]
+#[command(
+    version,
+    about,
+    long_about = None,
+    after_help = "Running w/out a subcommand solves the given maze, e.g. `maze-robot maze.txt`"
+)]
 struct App {
-    maze_file: String,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Send a robot through a maze & print the path it found
+    Solve(SolveArgs),
+    /// Print a new random maze
+    Generate(GenerateArgs),
+    /// Check a maze can be solved before sending a robot into it
+    Validate(ValidateArgs),
+    /// Print a maze
+    Render(RenderArgs),
+    /// Time how long a solver takes on a maze
+    Bench(BenchArgs),
+    /// Send a robot through a maze rebuilt from a trace written by `solve --record`
+    Replay(ReplayArgs),
+    /// Explore a maze, then drive the shortest path found back from the start in a second run
+    Race(RaceArgs),
+    /// Host a maze for robots elsewhere, answering `PEEK <dir>` & `GO <dir>` lines
+    Serve(ServeArgs),
+    /// Send a robot through a maze hosted by `serve`
+    Connect(ConnectArgs),
+}
+
+#[derive(Args)]
+struct SolveArgs {
+    #[command(flatten)]
+    source: MazeSource,
+    /// Strategy the robot uses to search the maze
+    #[arg(long, value_enum, default_value_t)]
+    solver: Solver,
+    /// How to print the solution
+    #[arg(long, value_enum, default_value_t)]
+    output: OutputFormat,
+    /// Redraw the maze on stderr every time the robot moves, for mazes w/ block walls
+    #[arg(long)]
+    animate: bool,
+    /// Milliseconds to pause on each frame when animating
+    #[arg(long, default_value_t = 100)]
+    delay: u64,
+    #[command(flatten)]
+    theme: Theme,
+    /// Write every look & move the robot makes to this trace file
+    #[arg(long)]
+    record: Option<PathBuf>,
+    /// Print how much work the search took to stderr
+    #[arg(long)]
+    stats: bool,
+    /// Write the map the robot built while searching to this file, as a text maze w/ '?' for
+    /// cells it never saw, which its legend makes walls. Mazes w/ walls along cell edges can't be
+    /// written this way
+    #[arg(long)]
+    map: Option<PathBuf>,
+    /// Once at the finish, drive back to the start along the shortest route the robot knows of
+    #[arg(long)]
+    return_home: bool,
+}
+
+#[derive(Args)]
+struct GenerateArgs {
+    /// Number of rooms across
+    #[arg(long, default_value_t = 8, value_parser = parse_rooms)]
+    width: usize,
+    /// Number of rooms down
+    #[arg(long, default_value_t = 8, value_parser = parse_rooms)]
+    height: usize,
+    /// Seed for the random layout, defaults to the current time
+    #[arg(long)]
+    seed: Option<u64>,
+}
+
+#[derive(Args)]
+struct ValidateArgs {
+    #[command(flatten)]
+    source: MazeSource,
+    /// Also fail if the maze has loops or unreachable areas
+    #[arg(long)]
+    require_perfect: bool,
+}
+
+#[derive(Args)]
+struct RenderArgs {
+    #[command(flatten)]
+    source: MazeSource,
+    /// Solve the maze w/ this solver & draw its path on top
+    #[arg(long, value_enum)]
+    solver: Option<Solver>,
+    /// How to draw cells on the solution path
+    #[arg(long, value_enum, default_value_t)]
+    glyphs: PathGlyphs,
+    /// Draw the maze as text or as an image
+    #[arg(long, value_enum, default_value_t)]
+    format: RenderFormat,
+    /// SVG only: shade every cell the robot stood in
+    #[arg(long, requires = "solver")]
+    visited: bool,
+    /// SVG only: shade cells by how many times the robot stood in them
+    #[arg(long, requires = "solver")]
+    heatmap: bool,
+    /// Text only: draw just the part of the maze around this `col,row` cell
+    #[arg(long, value_parser = parse_posn, conflicts_with = "solver")]
+    around: Option<(usize, usize)>,
+    /// How many cells to draw on each side of `--around`
+    #[arg(long, default_value_t = 20, requires = "around")]
+    radius: usize,
+    #[command(flatten)]
+    theme: Theme,
+}
+
+#[derive(Args)]
+struct BenchArgs {
+    #[command(flatten)]
+    source: MazeSource,
+    /// Strategy the robot uses to search the maze
+    #[arg(long, value_enum, default_value_t)]
+    solver: Solver,
+    /// Number of times to solve the maze
+    #[arg(long, default_value_t = 100)]
+    iterations: u32,
+    /// Solve w/ this many robots at once, each in its own copy of the maze, all searching on one
+    /// async executor
+    #[arg(long)]
+    robots: Option<u32>,
+}
+
+#[derive(Args)]
+struct ReplayArgs {
+    /// Trace file written by `solve --record`
+    trace: PathBuf,
+    /// Strategy the robot uses to search the maze
+    #[arg(long, value_enum, default_value_t)]
+    solver: Solver,
+    /// How to print the solution
+    #[arg(long, value_enum, default_value_t)]
+    output: OutputFormat,
+    /// Print how much work the search took to stderr
+    #[arg(long)]
+    stats: bool,
+}
+
+#[derive(Args)]
+struct RaceArgs {
+    #[command(flatten)]
+    source: MazeSource,
+    /// Strategy the robot uses to explore the maze
+    #[arg(long, value_enum, default_value_t)]
+    solver: Solver,
+    /// How to print the path driven in the second run
+    #[arg(long, value_enum, default_value_t)]
+    output: OutputFormat,
+    /// Write the map built while exploring to this file, as a text maze, which can't be done for
+    /// mazes w/ walls along cell edges
+    #[arg(long)]
+    map: Option<PathBuf>,
+    /// Skip exploring & drive the shortest path on a map written by an earlier `--map`
+    #[arg(long, conflicts_with_all = ["solver", "map"])]
+    known_map: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct ServeArgs {
+    #[command(flatten)]
+    source: MazeSource,
+    /// Listen on this TCP address, like `127.0.0.1:7878`, instead of stdin & stdout
+    #[arg(long)]
+    tcp: Option<String>,
+    /// Listen on a Unix socket at this path, instead of stdin & stdout
+    #[cfg(unix)]
+    #[arg(long, conflicts_with = "tcp")]
+    unix: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct ConnectArgs {
+    /// TCP address the maze is served on
+    #[cfg_attr(unix, arg(long, required_unless_present = "unix"))]
+    #[cfg_attr(not(unix), arg(long, required = true))]
+    tcp: Option<String>,
+    /// Unix socket the maze is served on
+    #[cfg(unix)]
+    #[arg(long, conflicts_with = "tcp")]
+    unix: Option<PathBuf>,
+    /// Strategy the robot uses to search the maze
+    #[arg(long, value_enum, default_value_t)]
+    solver: Solver,
+    /// How to print the solution
+    #[arg(long, value_enum, default_value_t)]
+    output: OutputFormat,
+    /// Print how much work the search took to stderr
+    #[arg(long)]
+    stats: bool,
+}
+
+fn main() -> ExitCode {
+    let app = App::parse_from(with_default_subcommand(args_os().collect()));
+
+    let result = match app.command {
+        Command::Solve(args) => solve(args),
+        Command::Generate(args) => {
+            generate_maze(args);
+            Ok(())
+        }
+        Command::Validate(args) => validate(args),
+        Command::Render(args) => render(args),
+        Command::Bench(args) => bench(args),
+        Command::Replay(args) => replay(args),
+        Command::Race(args) => race(args),
+        Command::Serve(args) => serve_maze(args),
+        Command::Connect(args) => connect(args),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// `solve` is the default subcommand, so slip it in when the arguments don't name another one.
+fn with_default_subcommand(mut args: Vec<OsString>) -> Vec<OsString> {
+    let app = App::command();
+    let names_other = args.get(1).and_then(|arg| arg.to_str()).is_none_or(|arg| {
+        matches!(arg, "help" | "-h" | "--help" | "-V" | "--version")
+            || app.find_subcommand(arg).is_some()
+    });
+
+    if !names_other {
+        args.insert(1, OsString::from("solve"));
+    }
+    args
+}
+
+fn solve(
+    SolveArgs {
+        source,
+        solver,
+        output,
+        animate,
+        delay,
+        theme,
+        record,
+        stats,
+        map,
+        return_home,
+    }: SolveArgs,
+) -> anyhow::Result<()> {
+    let maze: Box<dyn Maze> = if animate {
+        // redrawn w/ block walls, the maze would be a different one to the robot
+        if source.has_edge_walls() {
+            return Err(anyhow!(
+                "--animate can only draw mazes w/ block walls, not walls along cell edges"
+            ));
+        }
+        let maze = source.parse(&source.read()?)?;
+        // frames go to stderr, so they don't get mixed into the solution on stdout
+        Box::new(AnimatedMaze::new(
+            maze,
+            stderr(),
+            Duration::from_millis(delay),
+            theme,
+        ))
+    } else {
+        source.maze()?
+    };
+    let robot = match record {
+        Some(path) => {
+            let trace = BufWriter::new(File::create(path)?);
+            RobotInternal::new(RecordingMaze::new(maze, trace))
+        }
+        None => RobotInternal::new(maze),
+    };
+    // mapping costs memory for every cell seen, so only pay for it when asked
+    if map.is_none() && !return_home {
+        return print_run(solver, &search(solver, robot, stats)?, output, stats);
+    }
+
+    let robot = MappingRobot::new(robot);
+    let run = search(solver, &robot, stats)?;
+    let home = return_home.then(|| go_home(&robot)).transpose()?;
+
+    if let Some(path) = map {
+        write(path, robot.map().to_maze()?)?;
+    }
+
+    let Some(home) = home else {
+        return print_run(solver, &run, output, stats);
+    };
+
+    println!("{}", output.format_round_trip(solver, &run, &home)?);
+    if stats {
+        eprintln!("outbound:\n{}\n", run.stats);
+        eprintln!("home:\n{}\n", home.stats);
+    }
+    eprintln!("outbound moves: {}", run.stats.moves);
+    eprintln!("home moves:     {}", home.stats.moves);
+
+    Ok(())
+}
+
+fn generate_maze(
+    GenerateArgs {
+        width,
+        height,
+        seed,
+    }: GenerateArgs,
+) {
+    let seed = seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or_default()
+    });
+
+    println!("{}", generate(width, height, seed));
+}
+
+/// A count of rooms for `generate`, which needs at least one to put the start & finish in.
+fn parse_rooms(value: &str) -> anyhow::Result<usize> {
+    match value.trim().parse()? {
+        0 => Err(anyhow!("a maze needs at least one room")),
+        rooms => Ok(rooms),
+    }
+}
+
+fn validate(
+    ValidateArgs {
+        source,
+        require_perfect,
+    }: ValidateArgs,
+) -> anyhow::Result<()> {
+    let maze_text = source.read()?;
+    let report = analyze(&source.parse(&maze_text)?);
+    let maze_file = source.maze_file;
+
+    println!("{report}");
+
+    if !report.finish_reachable() {
+        Err(anyhow!("{maze_file}: finish cannot be reached from start"))
+    } else if require_perfect && !report.perfect {
+        Err(anyhow!("{maze_file}: maze is not perfect"))
+    } else {
+        Ok(())
+    }
+}
+
+fn render(
+    RenderArgs {
+        source,
+        solver,
+        glyphs,
+        format,
+        visited,
+        heatmap,
+        around,
+        radius,
+        theme,
+    }: RenderArgs,
+) -> anyhow::Result<()> {
+    let maze_text = source.read()?;
+    let maze = source.parse(&maze_text)?;
+    let run = solver
+        .map(|solver| anyhow::Ok(solver.run(TextRobot::new(source.parse(&maze_text)?))?))
+        .transpose()?;
+
+    match (format, run) {
+        (RenderFormat::Svg, run) => {
+            let layers = Layers {
+                path: run.is_some(),
+                visited,
+                heatmap,
+            };
+            print!("{}", to_svg(&maze, run.as_ref(), layers));
+        }
+        (RenderFormat::Pbm, _) => stdout().write_all(&write_netpbm(&maze, Netpbm::Pbm))?,
+        (RenderFormat::Pgm, _) => stdout().write_all(&write_netpbm(&maze, Netpbm::Pgm))?,
+        (RenderFormat::Json, _) => println!(
+            "{}",
+            serde_json::to_string_pretty(&JsonMaze::from_text_maze(&maze, source.name()))?
+        ),
+        (RenderFormat::Text, Some(run)) => println!("{}", render_run(&maze, &run, glyphs, theme)),
+        (RenderFormat::Text, None) => match around {
+            Some(posn) => println!("{}", maze.window(posn, radius)),
+            None => println!("{}", render_marked(&maze, &HashMap::new(), theme)),
+        },
+    }
+
+    Ok(())
+}
+
+fn bench(
+    BenchArgs {
+        source,
+        solver,
+        iterations,
+        robots,
+    }: BenchArgs,
+) -> anyhow::Result<()> {
+    let mut times = Vec::with_capacity(iterations as usize);
+    let mut path_len = 0;
+
+    for _ in 0..iterations.max(1) {
+        match robots {
+            Some(robots) => {
+                let robots = (0..robots.max(1))
+                    .map(|_| Ok(AsyncRobotInternal::new(Blocking(source.maze()?))))
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                let begin = Instant::now();
+                let runs = block_on(join_all(
+                    robots.into_iter().map(|robot| solver.run_async(robot)),
+                ));
+                times.push(begin.elapsed());
+                for run in runs {
+                    path_len = run?.path.len();
+                }
+            }
+            None => {
+                let robot = source.robot()?;
+                let begin = Instant::now();
+                path_len = solver.run(robot)?.path.len();
+                times.push(begin.elapsed());
+            }
+        }
+    }
+
+    let total: Duration = times.iter().sum();
+    let min = times.iter().min().copied().unwrap_or_default();
+    let max = times.iter().max().copied().unwrap_or_default();
+
+    println!("solver:     {solver:?}");
+    if let Some(robots) = robots {
+        println!("robots:     {}", robots.max(1));
+    }
+    println!("iterations: {}", times.len());
+    println!("path cells: {path_len}");
+    println!("mean:       {:?}", total / times.len() as u32);
+    println!("min:        {min:?}");
+    println!("max:        {max:?}");
+
+    Ok(())
+}
+
+fn replay(
+    ReplayArgs {
+        trace,
+        solver,
+        output,
+        stats,
+    }: ReplayArgs,
+) -> anyhow::Result<()> {
+    let maze = ReplayMaze::read(&read_to_string(trace)?)?;
+    let run = search(solver, RobotInternal::new(maze), stats)?;
+
+    print_run(solver, &run, output, stats)
+}
+
+fn race(
+    RaceArgs {
+        source,
+        solver,
+        output,
+        map: map_file,
+        known_map,
+    }: RaceArgs,
+) -> anyhow::Result<()> {
+    let (map, explored) = match known_map {
+        Some(path) => (read_to_string(path)?.parse::<KnownMap>()?, None),
+        None => {
+            let robot = MappingRobot::new(RobotInternal::new(source.maze()?));
+            let run = solver.run(&robot)?;
+            (robot.map(), Some(run))
+        }
+    };
+
+    if let Some(path) = map_file {
+        write(path, map.to_maze()?)?;
+    }
+
+    let path = map
+        .shortest_path()
+        .ok_or(anyhow!("no finish can be reached on the known map"))?;
+    let run = drive(RobotInternal::new(source.maze()?), &path)?;
+
+    println!("{}", output.format(solver, &run)?);
+    // kept off stdout, like `--stats`, so the path can still be piped elsewhere
+    match explored {
+        Some(explored) => eprintln!("explore moves:   {}", explored.stats.moves),
+        None => eprintln!("explore moves:   skipped"),
+    }
+    eprintln!("speed run moves: {}", run.stats.moves);
+
+    Ok(())
 }
 
-fn main() -> anyhow::Result<()> {
-    let app = App::parse();
-    let maze_text = read_to_string(app.maze_file)?;
+fn serve_maze(args: ServeArgs) -> anyhow::Result<()> {
+    #[cfg(unix)]
+    if let Some(path) = &args.unix {
+        return serve_unix(&args.source, path);
+    }
+
+    // robots are served one at a time, each in a fresh copy of the maze
+    match args.tcp {
+        Some(addr) => {
+            let listener = TcpListener::bind(addr)?;
+            eprintln!(
+                "serving {} on {}",
+                args.source.maze_file,
+                listener.local_addr()?
+            );
+            for stream in listener.incoming() {
+                let stream = stream?;
+                session(&args.source, BufReader::new(stream.try_clone()?), stream);
+            }
+        }
+        None => serve(&mut args.source.maze()?, stdin().lock(), stdout().lock())?,
+    }
+
+    Ok(())
+}
+
+#[cfg(unix)]
+fn serve_unix(source: &MazeSource, path: &Path) -> anyhow::Result<()> {
+    // a socket nothing answers on was left behind by a server that was stopped
+    let stale = symlink_metadata(path).is_ok_and(|meta| meta.file_type().is_socket())
+        && UnixStream::connect(path).is_err();
+    if stale {
+        remove_file(path)?;
+    }
+
+    let listener = UnixListener::bind(path)?;
+    let _socket = SocketFile(path);
+    eprintln!("serving {} on {}", source.maze_file, path.display());
+    for stream in listener.incoming() {
+        let stream = stream?;
+        session(source, BufReader::new(stream.try_clone()?), stream);
+    }
+
+    Ok(())
+}
+
+/// A socket file to remove once the server listening on it stops.
+#[cfg(unix)]
+struct SocketFile<'a>(&'a Path);
+
+#[cfg(unix)]
+impl Drop for SocketFile<'_> {
+    fn drop(&mut self) {
+        let _ = remove_file(self.0);
+    }
+}
+
+/// Serve one robot, reporting rather than returning anything that goes wrong so the next robot
+/// can still connect.
+fn session<R: BufRead, W: Write>(source: &MazeSource, input: R, out: W) {
+    let result = source
+        .maze()
+        .and_then(|mut maze| Ok(serve(&mut maze, input, out)?));
+
+    if let Err(e) = result {
+        eprintln!("error: {e:#}");
+    }
+}
+
+fn connect(args: ConnectArgs) -> anyhow::Result<()> {
+    let run = search_remote(&args)?;
+
+    print_run(args.solver, &run, args.output, args.stats)
+}
+
+/// Search a maze served elsewhere, wherever `args` says it is.
+fn search_remote(args: &ConnectArgs) -> anyhow::Result<Run> {
+    #[cfg(unix)]
+    if let Some(path) = &args.unix {
+        let stream = UnixStream::connect(path)?;
+        let maze = RemoteMaze::new(path.display().to_string(), stream.try_clone()?, stream);
+        return search_remote_maze(maze, args);
+    }
+
+    let addr = args
+        .tcp
+        .clone()
+        .ok_or(anyhow!("give the maze's address w/ --tcp or --unix"))?;
+    let stream = TcpStream::connect(&addr)?;
+
+    search_remote_maze(RemoteMaze::new(addr, stream.try_clone()?, stream), args)
+}
+
+/// Search a remote maze, blaming the connection if the solver gave up after it broke.
+fn search_remote_maze<R: Read + 'static, W: Write + 'static>(
+    maze: RemoteMaze<R, W>,
+    args: &ConnectArgs,
+) -> anyhow::Result<Run> {
+    let failure = maze.failure();
+
+    search(args.solver, RobotInternal::new(maze), args.stats).map_err(|e| match failure.take() {
+        Some(cause) => {
+            anyhow::Error::from(cause).context("Error encountered while talking to the maze.")
+        }
+        None => e,
+    })
+}
+
+/// Search w/ a solver, still printing the stats asked for if it gives up.
+fn search<R: Robot>(solver: Solver, robot: R, stats: bool) -> anyhow::Result<Run> {
+    solver.run(robot).map_err(|failed| {
+        // the solver's own counts went w/ it, so those print as zero
+        if stats {
+            eprintln!("{}", failed.stats);
+        }
+        failed.into()
+    })
+}
 
-    println!("Solution: {:#?}", solve(maze_text.as_str())?);
+/// Print a solution, w/ its stats on stderr so they don't get mixed into the solution itself.
+fn print_run(solver: Solver, run: &Run, output: OutputFormat, stats: bool) -> anyhow::Result<()> {
+    println!("{}", output.format(solver, run)?);
+    if stats {
+        eprintln!("{}", run.stats);
+    }
 
     Ok(())
 }
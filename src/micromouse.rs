@@ -0,0 +1,145 @@
+use maze_robot::controller::MazeError;
+
+use crate::{
+    edge_maze::{Drawing, EdgeMaze},
+    finish::KnownFinish,
+};
+
+/// Name given to the format in errors.
+const FORMAT: &str = "Micromouse maze";
+
+/// Read a micromouse maze drawn w/ 'o' or '+' posts, `---` walls & `|` walls, like:
+///
+/// ```text
+/// o---o---o---o---o
+/// |               |
+/// o   o---o   o   o
+/// ...
+/// ```
+///
+/// The start is the cell marked 'S', or the bottom left cell if none is. The goal is always the
+/// 2x2 region in the centre, so any cells marked as finishes are ignored, & the robot is told
+/// where it is like a micromouse would be.
+pub fn read_micromouse_text(text: &str) -> Result<EdgeMaze, MazeError> {
+    let drawing = Drawing::parse(text)?;
+    let goal = centre(drawing.width, drawing.height)?;
+    let start = drawing
+        .start
+        .map_or((0, drawing.height - 1), |(posn, _)| posn);
+
+    Ok(drawing.into_maze(start, &goal)?.with_known_finish())
+}
+
+/// Read a binary `.maz` file: one byte per cell of a square maze, w/ walls in the low bits as
+/// N=1, E=2, S=4 & W=8.
+///
+/// Cells are stored a column at a time from the bottom left, so the first bytes are the western
+/// column from south to north. The start is the bottom left cell & the goal the 2x2 centre, which
+/// the robot is told about.
+pub fn read_maz(bytes: &[u8]) -> Result<EdgeMaze, MazeError> {
+    let side = bytes.len().isqrt();
+    if side * side != bytes.len() {
+        return Err(MazeError::creation(
+            FORMAT,
+            &format!("expected a square maze but found {} cells", bytes.len()),
+        ));
+    }
+    let goal = centre(side, side)?;
+
+    // our rows run from the top, .maz columns from the bottom
+    let walls = (0..side * side)
+        .map(|idx| {
+            let (col, row) = (idx % side, idx / side);
+            bytes[col * side + (side - 1 - row)] & 0x0f
+        })
+        .collect();
+
+    Ok(EdgeMaze::new(side, side, walls, (0, side - 1), &goal)?.with_known_finish())
+}
+
+/// The 4 cells in the middle of a maze, as (column, row) positions.
+fn centre(width: usize, height: usize) -> Result<[(usize, usize); 4], MazeError> {
+    if width < 2 || height < 2 || width % 2 == 1 || height % 2 == 1 {
+        return Err(MazeError::creation(
+            FORMAT,
+            &format!("a {width}x{height} maze has no 2x2 centre, both sides must be even"),
+        ));
+    }
+    let (col, row) = (width / 2 - 1, height / 2 - 1);
+
+    Ok([
+        (col, row),
+        (col + 1, row),
+        (col, row + 1),
+        (col + 1, row + 1),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use maze_robot::controller::{Direction, Maze};
+
+    use super::*;
+
+    const MAZE: &str = "\
+o---o---o---o---o
+|           |   |
+o   o---o   o   o
+|   |       |   |
+o   o   o---o   o
+|   |           |
+o   o---o---o   o
+|               |
+o---o---o---o---o";
+
+    #[rstest]
+    fn test_text_starts_bottom_left_w_centre_goal() {
+        let maze = read_micromouse_text(MAZE).expect("maze to load");
+
+        assert_eq!(
+            maze.to_block_text(),
+            "\
++++++++++
++     + +
++ +++ + +
++ +F F+ +
++ + +++ +
++ +F F  +
++ +++++ +
++S      +
++++++++++"
+        )
+    }
+
+    #[rstest]
+    fn test_maz_matches_text() {
+        // walls listed column by column from the bottom left, to match MAZE
+        let bytes = [
+            12, 10, 10, 9, //
+            5, 12, 9, 5, //
+            5, 5, 6, 3, //
+            6, 2, 10, 11,
+        ];
+        let mut maze = read_maz(&bytes).expect("maze to load");
+
+        assert_eq!(
+            maze.to_block_text(),
+            read_micromouse_text(MAZE)
+                .expect("maze to load")
+                .to_block_text()
+        );
+        assert!(maze.move_dir(Direction::North).is_ok());
+    }
+
+    #[rstest]
+    #[case::not_square(&[0; 12])]
+    #[case::odd_side(&[0; 9])]
+    fn test_maz_invalid(#[case] bytes: &[u8]) {
+        match read_maz(bytes) {
+            Err(MazeError::CreationError(_)) => (),
+            other => panic!("expected CreationError, got {other:?}"),
+        }
+    }
+}
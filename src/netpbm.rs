@@ -0,0 +1,323 @@
+use maze_robot::controller::{Cell, MazeError};
+
+use crate::text_maze::TextMaze;
+
+/// Name given to the format in errors.
+const FORMAT: &str = "Netpbm image";
+
+/// Grey level written for the start cell, & looked for when no start position is given.
+pub const START_GREY: u16 = 64;
+/// Grey level written for finish cells, & looked for alongside any given finish positions.
+pub const FINISH_GREY: u16 = 192;
+
+/// Where the start & finish are in an image, since plain black & white can't show them.
+///
+/// Positions are (column, row) pixels. Grey levels are only matched in PGM images & use the
+/// image's own max value as full white.
+#[derive(Clone, Debug)]
+pub struct Markers {
+    pub start: Option<(usize, usize)>,
+    pub finish: Vec<(usize, usize)>,
+    pub start_grey: u16,
+    pub finish_grey: u16,
+}
+
+impl Default for Markers {
+    fn default() -> Self {
+        Self {
+            start: None,
+            finish: vec![],
+            start_grey: START_GREY,
+            finish_grey: FINISH_GREY,
+        }
+    }
+}
+
+/// Image formats a maze can be written out as.
+#[derive(Clone, Copy, Debug)]
+pub enum Netpbm {
+    /// Binary black & white bitmap (P4), walls black, everything else white
+    Pbm,
+    /// Binary greymap (P5), walls black, open cells white, start & finish at their marker greys
+    Pgm,
+}
+
+/// Convert a PBM (P1/P4) or PGM (P2/P5) image into the text maze format, one pixel per cell.
+///
+/// Pixels darker than half of full white are walls & the rest are open, unless they are marked
+/// as the start or a finish.
+pub fn read_netpbm(bytes: &[u8], markers: &Markers) -> Result<String, MazeError> {
+    let mut header = Header { bytes, pos: 0 };
+    let magic = header.magic()?;
+    let width = header.number()?;
+    let height = header.number()?;
+    let max = match magic {
+        b'1' | b'4' => 1,
+        _ => header.number()?,
+    };
+    if width == 0 || height == 0 || max == 0 || max > u16::MAX as usize {
+        return Err(MazeError::creation(
+            FORMAT,
+            "image has invalid dimensions or max value",
+        ));
+    }
+
+    let pixels: Vec<u16> = match magic {
+        // plain formats are whitespace separated numbers, except PBM may pack its digits
+        b'1' => header
+            .rest()
+            .iter()
+            .filter(|b| matches!(b, b'0' | b'1'))
+            .map(|b| u16::from(b - b'0'))
+            .collect(),
+        b'2' => header
+            .rest()
+            .split(|b| b.is_ascii_whitespace())
+            .filter(|word| !word.is_empty())
+            .map(|word| {
+                std::str::from_utf8(word)
+                    .ok()
+                    .and_then(|w| w.parse().ok())
+                    .ok_or(MazeError::creation(FORMAT, "image has a non-numeric pixel"))
+            })
+            .collect::<Result<_, _>>()?,
+        // raw PBM packs 8 pixels per byte, padding each row to a whole byte
+        b'4' => {
+            let row_bytes = width.div_ceil(8);
+            let raster = header.raster()?;
+            if raster.len() < row_bytes * height {
+                return Err(MazeError::creation(
+                    FORMAT,
+                    "image has fewer pixels than its dimensions",
+                ));
+            }
+
+            raster
+                .chunks(row_bytes)
+                .flat_map(|row| {
+                    (0..width).map(move |col| u16::from(row[col / 8] >> (7 - col % 8) & 1))
+                })
+                .collect()
+        }
+        // raw PGM uses one byte per pixel, or two big endian bytes for deep images
+        _ if max < 256 => header.raster()?.iter().map(|&b| u16::from(b)).collect(),
+        _ => header
+            .raster()?
+            .chunks_exact(2)
+            .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+            .collect(),
+    };
+    if pixels.len() < width * height {
+        return Err(MazeError::creation(
+            FORMAT,
+            "image has fewer pixels than its dimensions",
+        ));
+    }
+
+    let is_pbm = matches!(magic, b'1' | b'4');
+    let mut rows: Vec<Vec<char>> = pixels
+        .chunks(width)
+        .take(height)
+        .map(|row| {
+            row.iter()
+                .map(|&px| match px {
+                    // PBM uses 1 for black
+                    1 if is_pbm => '+',
+                    _ if is_pbm => ' ',
+                    _ if px == markers.start_grey && markers.start.is_none() => 'S',
+                    _ if px == markers.finish_grey => 'F',
+                    _ if (px as usize) * 2 < max => '+',
+                    _ => ' ',
+                })
+                .collect()
+        })
+        .collect();
+
+    for &(col, row) in markers.start.iter().chain(&markers.finish) {
+        if col >= width || row >= height {
+            return Err(MazeError::creation(
+                FORMAT,
+                &format!("marker ({col},{row}) is outside the {width}x{height} image"),
+            ));
+        }
+    }
+    if let Some((col, row)) = markers.start {
+        rows[row][col] = 'S';
+    }
+    for &(col, row) in &markers.finish {
+        rows[row][col] = 'F';
+    }
+
+    Ok(rows
+        .into_iter()
+        .map(|row| row.into_iter().collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+/// Write the maze out as a raw image, one pixel per cell.
+pub fn write_netpbm(maze: &TextMaze, format: Netpbm) -> Vec<u8> {
+    let (width, height) = (maze.width(), maze.height());
+    let posns = (0..height).flat_map(|row| (0..width).map(move |col| (col, row)));
+
+    match format {
+        Netpbm::Pbm => {
+            let mut out = format!("P4\n{width} {height}\n").into_bytes();
+            let row_bytes = width.div_ceil(8);
+            let mut raster = vec![0u8; row_bytes * height];
+            for (col, row) in posns {
+                if maze.cell_at((col, row)) == Cell::Wall {
+                    raster[row * row_bytes + col / 8] |= 0x80 >> (col % 8);
+                }
+            }
+            out.extend(raster);
+            out
+        }
+        Netpbm::Pgm => {
+            let mut out = format!("P5\n{width} {height}\n255\n").into_bytes();
+            out.extend(posns.map(|posn| match maze.cell_at(posn) {
+                _ if posn == maze.start() => START_GREY as u8,
+                Cell::Wall => 0,
+                Cell::Finish => FINISH_GREY as u8,
+                Cell::Open => 255,
+            }));
+            out
+        }
+    }
+}
+
+/// Reads the whitespace & comment separated fields at the start of a Netpbm file.
+struct Header<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Header<'a> {
+    fn magic(&mut self) -> Result<u8, MazeError> {
+        match self.bytes {
+            [b'P', kind @ (b'1' | b'2' | b'4' | b'5'), ..] => {
+                self.pos = 2;
+                Ok(*kind)
+            }
+            _ => Err(MazeError::creation(
+                FORMAT,
+                "expected a P1, P2, P4 or P5 header",
+            )),
+        }
+    }
+
+    fn number(&mut self) -> Result<usize, MazeError> {
+        self.skip_blank();
+        let begin = self.pos;
+        while self.bytes.get(self.pos).is_some_and(u8::is_ascii_digit) {
+            self.pos += 1;
+        }
+
+        std::str::from_utf8(&self.bytes[begin..self.pos])
+            .ok()
+            .and_then(|digits| digits.parse().ok())
+            .ok_or(MazeError::creation(FORMAT, "header is missing a number"))
+    }
+
+    /// Everything after the header, for the plain formats.
+    fn rest(&self) -> &'a [u8] {
+        &self.bytes[self.pos..]
+    }
+
+    /// Everything after the single whitespace byte that ends the header, for the raw formats.
+    fn raster(&self) -> Result<&'a [u8], MazeError> {
+        self.bytes
+            .get(self.pos + 1..)
+            .ok_or(MazeError::creation(FORMAT, "image has no pixel data"))
+    }
+
+    fn skip_blank(&mut self) {
+        while let Some(&b) = self.bytes.get(self.pos) {
+            match b {
+                b'#' => {
+                    while self.bytes.get(self.pos).is_some_and(|&b| b != b'\n') {
+                        self.pos += 1;
+                    }
+                }
+                _ if b.is_ascii_whitespace() => self.pos += 1,
+                _ => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    const MAZE: &str = "\
++++++
+S   +
++++ F
++++++";
+
+    #[rstest]
+    fn test_read_plain_pbm_w_coordinates() {
+        let image = b"P1\n# a comment\n5 4\n11111\n00001\n11100\n11111\n";
+        let markers = Markers {
+            start: Some((0, 1)),
+            finish: vec![(4, 2)],
+            ..Default::default()
+        };
+
+        assert_eq!(read_netpbm(image, &markers).expect("image to load"), MAZE)
+    }
+
+    #[rstest]
+    fn test_read_plain_pgm_w_marker_greys() {
+        let image = b"P2 5 4 255\n\
+            0 0 0 0 0\n\
+            64 200 255 255 0\n\
+            0 10 0 255 192\n\
+            0 0 0 0 0\n";
+        let act = read_netpbm(image, &Markers::default()).expect("image to load");
+
+        assert_eq!(act, MAZE)
+    }
+
+    #[rstest]
+    #[case(Netpbm::Pgm)]
+    #[case(Netpbm::Pbm)]
+    fn test_round_trip(#[case] format: Netpbm) {
+        let maze = TextMaze::try_from(MAZE).expect("maze to create successfully");
+        let image = write_netpbm(&maze, format);
+        let markers = match format {
+            Netpbm::Pgm => Markers::default(),
+            Netpbm::Pbm => Markers {
+                start: Some((0, 1)),
+                finish: vec![(4, 2)],
+                ..Default::default()
+            },
+        };
+
+        assert_eq!(read_netpbm(&image, &markers).expect("image to load"), MAZE)
+    }
+
+    #[rstest]
+    #[case(b"P3 1 1 255 0 0 0".as_slice())]
+    #[case(b"P2 2 2 255 0 0 0".as_slice())]
+    #[case(b"P1 0 1".as_slice())]
+    fn test_read_invalid(#[case] image: &[u8]) {
+        match read_netpbm(image, &Markers::default()) {
+            Err(MazeError::CreationError(_)) => (),
+            other => panic!("expected CreationError, got {other:?}"),
+        }
+    }
+
+    #[rstest]
+    fn test_marker_outside_image() {
+        let markers = Markers {
+            start: Some((9, 9)),
+            ..Default::default()
+        };
+
+        assert!(read_netpbm(b"P1 1 1 0", &markers).is_err())
+    }
+}
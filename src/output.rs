@@ -0,0 +1,254 @@
+use clap::ValueEnum;
+use serde::Serialize;
+
+use maze_robot::controller::Direction;
+
+use crate::solution::{Key, Run, Solver};
+
+/// Ways a solution can be written out.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum OutputFormat {
+    /// Rust debug formatting of the path
+    #[default]
+    Debug,
+    /// Path coordinates, step count & solver stats as a JSON object
+    Json,
+    /// One `east,north` coordinate pair per line, w/ a header
+    Csv,
+    /// One letter per step, e.g. `EESSE`
+    Directions,
+    /// Run-length encoded steps, e.g. `E2S2E`
+    RunLength,
+}
+
+#[derive(Serialize)]
+struct JsonSolution<'a> {
+    solver: Solver,
+    #[serde(flatten)]
+    leg: JsonLeg<'a>,
+}
+
+/// A solution that drove back to the start after reaching the finish.
+#[derive(Serialize)]
+struct JsonRoundTrip<'a> {
+    solver: Solver,
+    outbound: JsonLeg<'a>,
+    home: JsonLeg<'a>,
+}
+
+#[derive(Serialize)]
+struct JsonLeg<'a> {
+    steps: usize,
+    path: &'a [Key],
+    stats: JsonStats,
+}
+
+#[derive(Serialize)]
+struct JsonStats {
+    peeks: usize,
+    moves: usize,
+    backtracks: usize,
+    cells_visited: usize,
+    max_depth: usize,
+    elapsed_ms: f64,
+}
+
+impl<'a> From<&'a Run> for JsonLeg<'a> {
+    fn from(run: &'a Run) -> Self {
+        Self {
+            steps: run.path.len().saturating_sub(1),
+            path: &run.path,
+            stats: JsonStats {
+                peeks: run.stats.peeks,
+                moves: run.stats.moves,
+                backtracks: run.stats.backtracks,
+                cells_visited: run.stats.cells_visited,
+                max_depth: run.stats.max_depth,
+                elapsed_ms: run.stats.elapsed.as_secs_f64() * 1000.0,
+            },
+        }
+    }
+}
+
+impl OutputFormat {
+    pub fn format(self, solver: Solver, run: &Run) -> anyhow::Result<String> {
+        Ok(match self {
+            Self::Debug => format!("Solution: {:#?}", run.path),
+            Self::Json => serde_json::to_string_pretty(&JsonSolution {
+                solver,
+                leg: run.into(),
+            })?,
+            Self::Csv => run
+                .path
+                .iter()
+                .fold(String::from("east,north"), |out, key| {
+                    format!("{out}\n{},{}", key.east(), key.north())
+                }),
+            Self::Directions => steps(&run.path).map(letter).collect(),
+            Self::RunLength => run_length(&run.path),
+        })
+    }
+
+    /// Write out a run to the finish & the drive back home from it as one solution.
+    ///
+    /// JSON keeps each leg in a section of its own, CSV says which leg each cell is on, & the
+    /// step formats give the whole way there & back.
+    pub fn format_round_trip(
+        self,
+        solver: Solver,
+        outbound: &Run,
+        home: &Run,
+    ) -> anyhow::Result<String> {
+        // home starts where outbound ends, so that cell is only counted once
+        let there_and_back: Vec<Key> = outbound
+            .path
+            .iter()
+            .chain(home.path.iter().skip(1))
+            .copied()
+            .collect();
+
+        Ok(match self {
+            Self::Debug => format!("Solution: {:#?}\nHome: {:#?}", outbound.path, home.path),
+            Self::Json => serde_json::to_string_pretty(&JsonRoundTrip {
+                solver,
+                outbound: outbound.into(),
+                home: home.into(),
+            })?,
+            Self::Csv => [("outbound", outbound), ("home", home)]
+                .into_iter()
+                .flat_map(|(leg, run)| run.path.iter().map(move |key| (leg, key)))
+                .fold(String::from("leg,east,north"), |out, (leg, key)| {
+                    format!("{out}\n{leg},{},{}", key.east(), key.north())
+                }),
+            Self::Directions => steps(&there_and_back).map(letter).collect(),
+            Self::RunLength => run_length(&there_and_back),
+        })
+    }
+}
+
+fn steps(path: &[Key]) -> impl Iterator<Item = Direction> + '_ {
+    path.windows(2)
+        .filter_map(|pair| pair[0].direction_to(&pair[1]))
+}
+
+pub fn letter(direction: Direction) -> char {
+    match direction {
+        Direction::North => 'N',
+        Direction::East => 'E',
+        Direction::South => 'S',
+        Direction::West => 'W',
+    }
+}
+
+/// Collapse repeated steps into the letter followed by the count, leaving single steps bare.
+fn run_length(path: &[Key]) -> String {
+    let mut runs: Vec<(Direction, usize)> = vec![];
+
+    for dir in steps(path) {
+        match runs.last_mut() {
+            Some((last, count)) if *last == dir => *count += 1,
+            _ => runs.push((dir, 1)),
+        }
+    }
+
+    runs.into_iter()
+        .map(|(dir, count)| match count {
+            1 => letter(dir).to_string(),
+            n => format!("{}{n}", letter(dir)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{
+        solution::{MappingRobot, go_home},
+        text_maze::TextRobot,
+    };
+
+    const MAZE: &str = "\
+S  +F
+++ + 
+++   ";
+
+    fn run() -> Run {
+        Solver::Dfs
+            .run(TextRobot::try_from(MAZE).expect("maze to create successfully"))
+            .expect("solution to be found")
+    }
+
+    #[rstest]
+    #[case(OutputFormat::Directions, "EESSEENN")]
+    #[case(OutputFormat::RunLength, "E2S2E2N2")]
+    #[case(
+        OutputFormat::Csv,
+        "east,north\n0,0\n1,0\n2,0\n2,-1\n2,-2\n3,-2\n4,-2\n4,-1\n4,0"
+    )]
+    fn test_text_formats(#[case] format: OutputFormat, #[case] exp: &str) {
+        let act = format
+            .format(Solver::Dfs, &run())
+            .expect("format to succeed");
+
+        assert_eq!(act, exp)
+    }
+
+    #[rstest]
+    fn test_json() {
+        let run = run();
+        let act: serde_json::Value = serde_json::from_str(
+            &OutputFormat::Json
+                .format(Solver::Dfs, &run)
+                .expect("format to succeed"),
+        )
+        .expect("output to be valid json");
+
+        assert_eq!(act["solver"], "dfs");
+        assert_eq!(act["steps"], 8);
+        assert_eq!(act["path"][3], serde_json::json!([2, -1]));
+        assert_eq!(act["stats"]["cells_visited"], run.visited().len());
+        assert_eq!(act["stats"]["moves"], run.stats.moves);
+    }
+
+    fn round_trip() -> (Run, Run) {
+        let robot = MappingRobot::new(TextRobot::try_from("S F").expect("maze to create"));
+        let outbound = Solver::Dfs.run(&robot).expect("solution to be found");
+        let home = go_home(&robot).expect("route home to be found");
+
+        (outbound, home)
+    }
+
+    #[rstest]
+    #[case(OutputFormat::Directions, "EEWW")]
+    #[case(OutputFormat::RunLength, "E2W2")]
+    #[case(
+        OutputFormat::Csv,
+        "leg,east,north\noutbound,0,0\noutbound,1,0\noutbound,2,0\nhome,2,0\nhome,1,0\nhome,0,0"
+    )]
+    fn test_round_trip_text_formats(#[case] format: OutputFormat, #[case] exp: &str) {
+        let (outbound, home) = round_trip();
+        let act = format
+            .format_round_trip(Solver::Dfs, &outbound, &home)
+            .expect("format to succeed");
+
+        assert_eq!(act, exp)
+    }
+
+    #[rstest]
+    fn test_round_trip_json_is_one_document() {
+        let (outbound, home) = round_trip();
+        let act: serde_json::Value = serde_json::from_str(
+            &OutputFormat::Json
+                .format_round_trip(Solver::Dfs, &outbound, &home)
+                .expect("format to succeed"),
+        )
+        .expect("output to be valid json");
+
+        assert_eq!(act["solver"], "dfs");
+        assert_eq!(act["outbound"]["steps"], 2);
+        assert_eq!(act["home"]["path"][0], serde_json::json!([2, 0]));
+        assert_eq!(act["home"]["stats"]["moves"], home.stats.moves);
+    }
+}
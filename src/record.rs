@@ -0,0 +1,441 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fmt::{Debug, Display, Write as _},
+    io::Write,
+};
+
+use serde::{Deserialize, Serialize};
+
+use maze_robot::controller::{Cell, Direction, Maze, MazeError};
+
+use crate::solution::{Key, most_specific};
+
+/// One call made on a maze, w/ what it gave back. Traces are written one event per line as JSON,
+/// like `{"call":"look","direction":"North","cell":"Wall"}`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "call", rename_all = "snake_case")]
+pub enum Event {
+    Look {
+        direction: Direction,
+        cell: Cell,
+    },
+    Move {
+        direction: Direction,
+        moved: bool,
+    },
+    /// Only written when the maze shares where its finish is, usually first as solvers ask
+    /// before setting off.
+    FinishOffsets {
+        offsets: Vec<(isize, isize)>,
+    },
+}
+
+/// A maze that writes every call made on it to a trace, so the run can be replayed later w/out
+/// the maze.
+#[derive(Debug)]
+pub struct RecordingMaze<M: Maze, W: Write + Debug> {
+    maze: M,
+    // looking doesn't change the maze, but still has to be written down
+    out: RefCell<W>,
+}
+
+impl<M: Maze, W: Write + Debug> RecordingMaze<M, W> {
+    pub fn new(maze: M, out: W) -> Self {
+        Self {
+            maze,
+            out: RefCell::new(out),
+        }
+    }
+
+    fn record(&self, event: &Event) {
+        let mut out = self.out.borrow_mut();
+        // a trace that fails to write shouldn't stop the robot
+        let _ = serde_json::to_writer(&mut *out, event);
+        let _ = writeln!(out);
+    }
+}
+
+impl<M: Maze, W: Write + Debug> Maze for RecordingMaze<M, W> {
+    fn look_dir(&self, direction: Direction) -> Cell {
+        let cell = self.maze.look_dir(direction);
+        self.record(&Event::Look { direction, cell });
+
+        cell
+    }
+
+    fn move_dir(&mut self, direction: Direction) -> Result<(), MazeError> {
+        let result = self.maze.move_dir(direction);
+        self.record(&Event::Move {
+            direction,
+            moved: result.is_ok(),
+        });
+
+        result
+    }
+
+    fn finish_offsets(&self) -> Option<Vec<(isize, isize)>> {
+        let offsets = self.maze.finish_offsets()?;
+        self.record(&Event::FinishOffsets {
+            offsets: offsets.clone(),
+        });
+
+        Some(offsets)
+    }
+}
+
+impl<M: Maze, W: Write + Debug> Display for RecordingMaze<M, W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.maze, f)
+    }
+}
+
+/// A maze rebuilt from a trace, answering each look & move the way the recorded maze did from
+/// the same spot.
+///
+/// Answers are keyed by position relative to the start rather than by order, so a solver that
+/// makes its calls in a different order still gets the same maze.
+///
+/// A look the trace never saw can only be answered as a wall, so the maze notes that the robot
+/// has gone off the recording & refuses every move from then on w/ `MazeError::Diverged`.
+#[derive(Debug)]
+pub struct ReplayMaze {
+    seen: HashMap<(Key, Direction), Cell>,
+    /// Finish cells the recorded maze shared, if it did.
+    finish: Option<Vec<Key>>,
+    loc: Key,
+    /// The first look the trace couldn't answer.
+    diverged: RefCell<Option<(Key, Direction)>>,
+}
+
+impl ReplayMaze {
+    pub fn new<I: IntoIterator<Item = Event>>(events: I) -> Self {
+        let mut seen = HashMap::new();
+        let mut finish = None;
+        let mut loc = Key::default();
+
+        for event in events {
+            match event {
+                Event::Look { direction, cell } => {
+                    seen.insert((loc, direction), cell);
+                }
+                Event::Move {
+                    direction,
+                    moved: true,
+                } => {
+                    seen.entry((loc, direction)).or_insert(Cell::Open);
+                    loc = loc.compute_in_dir(&direction);
+                }
+                Event::Move {
+                    direction,
+                    moved: false,
+                } => {
+                    seen.insert((loc, direction), Cell::Wall);
+                }
+                Event::FinishOffsets { offsets } => {
+                    let keys = offsets
+                        .iter()
+                        .map(|(east, north)| Key::new(loc.east() + east, loc.north() + north));
+                    finish = Some(keys.collect());
+                }
+            }
+        }
+
+        Self {
+            seen,
+            finish,
+            loc: Key::default(),
+            diverged: RefCell::new(None),
+        }
+    }
+
+    /// Read a trace written by a `RecordingMaze`.
+    pub fn read(trace: &str) -> Result<Self, MazeError> {
+        let events = trace
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(idx, line)| {
+                serde_json::from_str(line)
+                    .map_err(|e| MazeError::CreationError(format!("trace line {}: {e}", idx + 1)))
+            })
+            .collect::<Result<Vec<Event>, _>>()?;
+
+        Ok(Self::new(events))
+    }
+
+    /// Every cell the trace saw, w/ cells seen as open from any side counted as open.
+    fn cells(&self) -> HashMap<Key, Cell> {
+        let mut cells = HashMap::from([(Key::default(), Cell::Open)]);
+        for (&(from, direction), &cell) in &self.seen {
+            for (key, cell) in [(from, Cell::Open), (from.compute_in_dir(&direction), cell)] {
+                let seen = cells.entry(key).or_insert(cell);
+                *seen = most_specific(*seen, cell);
+            }
+        }
+
+        cells
+    }
+
+    /// Westmost & northmost offsets of anything seen, for laying the cells out on a grid.
+    fn corner(cells: &HashMap<Key, Cell>) -> (isize, isize) {
+        let west = cells.keys().map(Key::east).min().unwrap_or_default();
+        let north = cells.keys().map(Key::north).max().unwrap_or_default();
+
+        (west, north)
+    }
+}
+
+impl Maze for ReplayMaze {
+    fn look_dir(&self, direction: Direction) -> Cell {
+        match self.seen.get(&(self.loc, direction)) {
+            Some(&cell) => cell,
+            None => {
+                self.diverged
+                    .borrow_mut()
+                    .get_or_insert((self.loc, direction));
+                Cell::Wall
+            }
+        }
+    }
+
+    fn move_dir(&mut self, direction: Direction) -> Result<(), MazeError> {
+        let cell = self.look_dir(direction);
+        if let Some((key, dir)) = *self.diverged.borrow() {
+            return Err(MazeError::Diverged(format!(
+                "the robot looked {dir} from {key}, which the trace never saw"
+            )));
+        }
+
+        match cell {
+            Cell::Wall => {
+                let (west, north) = Self::corner(&self.cells());
+                Err(MazeError::MoveError {
+                    direction,
+                    position: (
+                        (self.loc.east() - west) as usize,
+                        (north - self.loc.north()) as usize,
+                    ),
+                    blocked_by: Cell::Wall,
+                    surroundings: self.to_string(),
+                })
+            }
+            _ => {
+                self.loc = self.loc.compute_in_dir(&direction);
+                Ok(())
+            }
+        }
+    }
+
+    fn finish_offsets(&self) -> Option<Vec<(isize, isize)>> {
+        self.finish.as_ref().map(|keys| {
+            keys.iter()
+                .map(|key| (key.east() - self.loc.east(), key.north() - self.loc.north()))
+                .collect()
+        })
+    }
+}
+
+/// Draws what the trace saw, w/ '?' for anything it never looked at.
+impl Display for ReplayMaze {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cells = self.cells();
+        let (west, north) = Self::corner(&cells);
+        let east = cells.keys().map(Key::east).max().unwrap_or_default();
+        let south = cells.keys().map(Key::north).min().unwrap_or_default();
+
+        for row in (south..=north).rev() {
+            for col in west..=east {
+                let key = Key::new(col, row);
+                f.write_char(match cells.get(&key) {
+                    _ if key == self.loc => 'X',
+                    _ if key == Key::default() => 'S',
+                    Some(Cell::Wall) => '+',
+                    Some(Cell::Open) => ' ',
+                    Some(Cell::Finish) => 'F',
+                    None => '?',
+                })?;
+            }
+            if row > south {
+                f.write_char('\n')?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use rstest::rstest;
+
+    use maze_robot::controller::RobotInternal;
+
+    use super::*;
+    use crate::{finish::KnownFinish, solution::Solver, text_maze::TextMaze};
+
+    const MAZE: &str = "\
++++++++
++   + F
+S +++ +
++     +
++++++++";
+
+    /// Trace output the test can still read after the robot has taken the maze.
+    #[derive(Clone, Debug, Default)]
+    struct Shared(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[rstest]
+    fn test_trace_lines() {
+        let maze = TextMaze::try_from("SF").expect("maze to create successfully");
+        let mut recording = RecordingMaze::new(maze, vec![]);
+        recording.look_dir(Direction::West);
+        recording
+            .move_dir(Direction::East)
+            .expect("move to succeed");
+
+        assert_eq!(
+            String::from_utf8(recording.out.into_inner()).expect("trace to be utf8"),
+            "{\"call\":\"look\",\"direction\":\"West\",\"cell\":\"Wall\"}\n\
+             {\"call\":\"move\",\"direction\":\"East\",\"moved\":true}\n"
+        );
+    }
+
+    #[rstest]
+    fn test_replay_matches_recorded_run(
+        #[values(Solver::Dfs, Solver::WallFollower)] solver: Solver,
+    ) {
+        let trace = Shared::default();
+        let maze = TextMaze::try_from(MAZE).expect("maze to create successfully");
+        let recorded = solver
+            .run(RobotInternal::new(RecordingMaze::new(maze, trace.clone())))
+            .expect("solution to be found");
+
+        let trace = String::from_utf8(trace.0.take()).expect("trace to be utf8");
+        let replay = ReplayMaze::read(&trace).expect("trace to load");
+        let replayed = solver
+            .run(RobotInternal::new(replay))
+            .expect("solution to be found");
+
+        assert_eq!(replayed.path, recorded.path);
+        assert_eq!(replayed.trail, recorded.trail);
+    }
+
+    #[rstest]
+    fn test_replay_knows_where_recorded_finish_is(
+        #[values(Solver::FloodFill, Solver::DStarLite)] solver: Solver,
+    ) {
+        let trace = Shared::default();
+        let maze = TextMaze::try_from(MAZE)
+            .expect("maze to create successfully")
+            .with_known_finish();
+        let recorded = solver
+            .run(RobotInternal::new(RecordingMaze::new(maze, trace.clone())))
+            .expect("solution to be found");
+
+        let trace = String::from_utf8(trace.0.take()).expect("trace to be utf8");
+        assert!(trace.starts_with("{\"call\":\"finish_offsets\",\"offsets\":[[6,1]]}\n"));
+        let replayed = solver
+            .run(RobotInternal::new(
+                ReplayMaze::read(&trace).expect("trace to load"),
+            ))
+            .expect("solution to be found");
+
+        assert_eq!(replayed.path, recorded.path);
+        assert_eq!(replayed.trail, recorded.trail);
+    }
+
+    #[rstest]
+    fn test_replay_draws_what_was_seen() {
+        let mut replay = ReplayMaze::new([
+            Event::Look {
+                direction: Direction::East,
+                cell: Cell::Open,
+            },
+            Event::Look {
+                direction: Direction::North,
+                cell: Cell::Wall,
+            },
+            Event::Move {
+                direction: Direction::East,
+                moved: true,
+            },
+            Event::Look {
+                direction: Direction::East,
+                cell: Cell::Finish,
+            },
+            Event::Look {
+                direction: Direction::South,
+                cell: Cell::Wall,
+            },
+        ]);
+
+        assert_eq!(replay.to_string(), "+??\nX F\n?+?");
+        replay.move_dir(Direction::East).expect("move to succeed");
+        assert_eq!(replay.to_string(), "+??\nSXF\n?+?");
+        assert!(matches!(
+            replay.move_dir(Direction::South),
+            Err(MazeError::MoveError {
+                position: (1, 1),
+                ..
+            })
+        ));
+    }
+
+    #[rstest]
+    fn test_replay_refuses_moves_once_diverged() {
+        let mut replay = ReplayMaze::new([Event::Move {
+            direction: Direction::East,
+            moved: true,
+        }]);
+
+        // the trace only knows the way east, so looking north leaves the recording behind
+        assert_eq!(replay.look_dir(Direction::North), Cell::Wall);
+        assert_eq!(
+            replay.move_dir(Direction::East),
+            Err(MazeError::Diverged(String::from(
+                "the robot looked North from (0,0), which the trace never saw"
+            )))
+        );
+    }
+
+    #[rstest]
+    fn test_replay_keeps_finish_seen_from_any_side() {
+        let replay = ReplayMaze::new([
+            Event::Look {
+                direction: Direction::East,
+                cell: Cell::Finish,
+            },
+            Event::Move {
+                direction: Direction::East,
+                moved: true,
+            },
+            Event::Look {
+                direction: Direction::West,
+                cell: Cell::Open,
+            },
+        ]);
+
+        assert_eq!(replay.cells().get(&Key::new(1, 0)), Some(&Cell::Finish));
+    }
+
+    #[rstest]
+    fn test_read_invalid_trace() {
+        match ReplayMaze::read("{\"call\":\"jump\"}") {
+            Err(MazeError::CreationError(msg)) => assert!(msg.starts_with("trace line 1")),
+            other => panic!("expected CreationError, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use std::{
+    cell::RefCell,
+    fmt::{Debug, Display},
+    io::{BufRead, BufReader, Read, Write},
+    rc::Rc,
+};
+
+use maze_robot::controller::{Cell, Direction, Maze, MazeError};
+
+use crate::{output::letter, solution::Key};
+
+/// Answer a robot's commands for a maze, one per line, until the input runs out.
+///
+/// Commands are `PEEK <dir>` & `GO <dir>`, w/ `<dir>` one of `N`, `E`, `S` or `W`. Each gets a
+/// one line reply:
+/// - `PEEK` answers `OPEN`, `WALL` or `FINISH` for the cell in that direction
+/// - `GO` answers `OPEN` or `FINISH` for the cell moved into, or `WALL` if the robot couldn't move
+/// - anything else answers `ERR` & why, leaving the robot where it was
+pub fn serve<M: Maze, R: BufRead, W: Write>(
+    maze: &mut M,
+    input: R,
+    mut out: W,
+) -> std::io::Result<()> {
+    for line in input.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let reply = match command(&line) {
+            Ok(Command::Peek(dir)) => reply(maze.look_dir(dir)),
+            Ok(Command::Go(dir)) => {
+                // what's moved into has to be seen before moving, as a maze can't look at the
+                // cell the robot is in
+                let cell = maze.look_dir(dir);
+                match maze.move_dir(dir) {
+                    Ok(()) => reply(cell),
+                    Err(_) => reply(Cell::Wall),
+                }
+            }
+            Err(e) => format!("ERR {e}"),
+        };
+
+        writeln!(out, "{reply}")?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+/// A line a robot can send.
+enum Command {
+    Peek(Direction),
+    Go(Direction),
+}
+
+fn command(line: &str) -> Result<Command, String> {
+    let mut words = line.split_whitespace();
+    let (Some(verb), Some(dir), None) = (words.next(), words.next(), words.next()) else {
+        return Err(format!("expected `PEEK <dir>` or `GO <dir>`, got '{line}'"));
+    };
+    let dir = match dir.to_ascii_uppercase().as_str() {
+        "N" => Direction::North,
+        "E" => Direction::East,
+        "S" => Direction::South,
+        "W" => Direction::West,
+        _ => return Err(format!("unknown direction '{dir}', expected N, E, S or W")),
+    };
+
+    match verb.to_ascii_uppercase().as_str() {
+        "PEEK" => Ok(Command::Peek(dir)),
+        "GO" => Ok(Command::Go(dir)),
+        _ => Err(format!("unknown command '{verb}', expected PEEK or GO")),
+    }
+}
+
+fn reply(cell: Cell) -> String {
+    String::from(match cell {
+        Cell::Open => "OPEN",
+        Cell::Wall => "WALL",
+        Cell::Finish => "FINISH",
+    })
+}
+
+/// A maze hosted elsewhere by `serve`, reached over any pair of streams such as a socket.
+///
+/// A maze that stops answering looks like walls all round, so a robot in it gives up rather than
+/// wandering off. Why it stopped is given back by the next move, or kept in `failure` for a
+/// robot that gives up w/out moving again.
+pub struct RemoteMaze<R: Read, W: Write> {
+    name: String,
+    conn: RefCell<(BufReader<R>, W)>,
+    /// What went wrong w/ a look, as looks can't fail.
+    failed: Rc<RefCell<Option<MazeError>>>,
+    /// Where the robot is, relative to the start.
+    loc: Key,
+    /// Westmost & northmost of every cell the robot has been in, for placing it on a grid.
+    corner: (isize, isize),
+}
+
+impl<R: Read, W: Write> RemoteMaze<R, W> {
+    pub fn new(name: impl Into<String>, input: R, out: W) -> Self {
+        Self {
+            name: name.into(),
+            conn: RefCell::new((BufReader::new(input), out)),
+            failed: Rc::default(),
+            loc: Key::default(),
+            corner: (0, 0),
+        }
+    }
+
+    /// Where a look that failed is kept until a move gives it back, for checking once the robot
+    /// has been handed to a solver.
+    pub fn failure(&self) -> Rc<RefCell<Option<MazeError>>> {
+        Rc::clone(&self.failed)
+    }
+
+    /// Send one command & wait for its reply.
+    fn ask(&self, verb: &str, direction: Direction) -> Result<Cell, MazeError> {
+        let mut conn = self.conn.borrow_mut();
+        let (input, out) = &mut *conn;
+        let failed = |e: std::io::Error| MazeError::Remote(format!("{}: {e}", self.name));
+
+        writeln!(out, "{verb} {}", letter(direction)).map_err(failed)?;
+        out.flush().map_err(failed)?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line).map_err(failed)? == 0 {
+            return Err(MazeError::Remote(format!("{} hung up", self.name)));
+        }
+        match line.trim() {
+            "OPEN" => Ok(Cell::Open),
+            "WALL" => Ok(Cell::Wall),
+            "FINISH" => Ok(Cell::Finish),
+            other => Err(MazeError::Remote(format!(
+                "{} answered '{other}' to {verb}",
+                self.name
+            ))),
+        }
+    }
+}
+
+impl<R: Read, W: Write> Maze for RemoteMaze<R, W> {
+    fn look_dir(&self, direction: Direction) -> Cell {
+        self.ask("PEEK", direction).unwrap_or_else(|e| {
+            self.failed.borrow_mut().get_or_insert(e);
+            Cell::Wall
+        })
+    }
+
+    fn move_dir(&mut self, direction: Direction) -> Result<(), MazeError> {
+        if let Some(e) = self.failed.take() {
+            return Err(e);
+        }
+
+        match self.ask("GO", direction)? {
+            Cell::Wall => Err(MazeError::MoveError {
+                direction,
+                position: (
+                    (self.loc.east() - self.corner.0) as usize,
+                    (self.corner.1 - self.loc.north()) as usize,
+                ),
+                blocked_by: Cell::Wall,
+                surroundings: self.to_string(),
+            }),
+            _ => {
+                self.loc = self.loc.compute_in_dir(&direction);
+                self.corner = (
+                    self.corner.0.min(self.loc.east()),
+                    self.corner.1.max(self.loc.north()),
+                );
+                Ok(())
+            }
+        }
+    }
+}
+
+impl<R: Read, W: Write> Debug for RemoteMaze<R, W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RemoteMaze")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The maze can't be drawn from here, so only says where it is.
+impl<R: Read, W: Write> Display for RemoteMaze<R, W> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "remote maze at {}", self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::{TcpListener, TcpStream},
+        thread,
+    };
+
+    use rstest::rstest;
+
+    use maze_robot::controller::RobotInternal;
+
+    use super::*;
+    use crate::{solution::Solver, text_maze::TextMaze};
+
+    fn served(maze: &str, input: &str) -> String {
+        let mut maze = TextMaze::try_from(maze).expect("maze to create successfully");
+        let mut out = vec![];
+        serve(&mut maze, input.as_bytes(), &mut out).expect("session to finish");
+
+        String::from_utf8(out).expect("replies to be utf8")
+    }
+
+    #[rstest]
+    #[case("PEEK E\nPEEK N", "OPEN\nWALL\n")]
+    #[case("GO E\nGO e\ngo E\nPEEK w", "OPEN\nFINISH\nWALL\nOPEN\n")]
+    #[case("\nGO N\n\nPEEK E", "WALL\nOPEN\n")]
+    fn test_answers_commands(#[case] input: &str, #[case] exp: &str) {
+        assert_eq!(served("S F", input), exp);
+    }
+
+    #[rstest]
+    #[case("JUMP E", "ERR unknown command 'JUMP', expected PEEK or GO\n")]
+    #[case("GO UP", "ERR unknown direction 'UP', expected N, E, S or W\n")]
+    #[case("PEEK", "ERR expected `PEEK <dir>` or `GO <dir>`, got 'PEEK'\n")]
+    fn test_rejects_bad_commands(#[case] input: &str, #[case] exp: &str) {
+        assert_eq!(served("S F", input), exp);
+    }
+
+    #[rstest]
+    fn test_solves_maze_served_over_tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("port to be free");
+        let addr = listener.local_addr().expect("listener to have an address");
+        let server = thread::spawn(move || {
+            let (stream, _) = listener.accept().expect("client to connect");
+            let mut maze = TextMaze::try_from("S +\n+ F").expect("maze to create successfully");
+            let input = BufReader::new(stream.try_clone().expect("stream to clone"));
+            serve(&mut maze, input, stream).expect("session to finish");
+        });
+
+        let stream = TcpStream::connect(addr).expect("server to accept");
+        let maze = RemoteMaze::new(
+            addr.to_string(),
+            stream.try_clone().expect("stream to clone"),
+            stream,
+        );
+        let act = Solver::Dfs
+            .run(RobotInternal::new(maze))
+            .expect("solution to be found");
+
+        assert_eq!(act.path.len(), 4);
+        server.join().expect("server to finish");
+    }
+
+    #[rstest]
+    fn test_hung_up_server_looks_like_walls() {
+        let mut maze = RemoteMaze::new("nowhere", "".as_bytes(), vec![]);
+
+        assert_eq!(maze.look_dir(Direction::East), Cell::Wall);
+        assert_eq!(
+            maze.move_dir(Direction::East),
+            Err(MazeError::Remote(String::from("nowhere hung up")))
+        );
+    }
+
+    #[rstest]
+    fn test_bad_reply_to_look_is_given_back_by_next_move() {
+        let mut maze = RemoteMaze::new("elsewhere", "MAYBE\nOPEN\n".as_bytes(), vec![]);
+
+        assert_eq!(maze.look_dir(Direction::East), Cell::Wall);
+        assert_eq!(
+            maze.move_dir(Direction::East),
+            Err(MazeError::Remote(String::from(
+                "elsewhere answered 'MAYBE' to PEEK"
+            )))
+        );
+        assert_eq!(maze.move_dir(Direction::East), Ok(()));
+    }
+
+    #[rstest]
+    fn test_failed_look_is_kept_for_robot_that_stops() {
+        let maze = RemoteMaze::new("nowhere", "".as_bytes(), vec![]);
+        let failure = maze.failure();
+        let robot = RobotInternal::new(maze);
+
+        assert!(Solver::Dfs.run(robot).is_err());
+        assert_eq!(
+            failure.take(),
+            Some(MazeError::Remote(String::from("nowhere hung up")))
+        );
+    }
+
+    #[rstest]
+    fn test_bumping_wall_is_move_error() {
+        let mut maze = RemoteMaze::new("elsewhere", "OPEN\nWALL\n".as_bytes(), vec![]);
+
+        assert_eq!(maze.move_dir(Direction::West), Ok(()));
+        assert!(matches!(
+            maze.move_dir(Direction::South),
+            Err(MazeError::MoveError {
+                direction: Direction::South,
+                position: (0, 0),
+                blocked_by: Cell::Wall,
+                ..
+            })
+        ));
+    }
+}
@@ -0,0 +1,279 @@
+use std::collections::HashMap;
+
+use clap::{Args, ValueEnum};
+
+use maze_robot::controller::{Cell, DIR_ARR, Direction};
+
+use crate::{
+    solution::{Key, Run},
+    text_maze::TextMaze,
+};
+
+/// What `render` produces.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum RenderFormat {
+    /// The maze as text, in the same format it is read in
+    #[default]
+    Text,
+    /// An SVG image
+    Svg,
+    /// A black & white PBM image, one pixel per cell
+    Pbm,
+    /// A greyscale PGM image, one pixel per cell, w/ the start & finish at their marker greys
+    Pgm,
+    /// A JSON maze w/ metadata
+    Json,
+}
+
+/// How cells on the solution path are drawn.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum PathGlyphs {
+    /// Arrows pointing toward the next cell on the path
+    #[default]
+    Arrows,
+    /// A '.' on every path cell
+    Dots,
+}
+
+/// Cells the robot stood in that did not end up on the path.
+pub const EXPLORED: char = ':';
+/// Cell the robot is standing in.
+pub const ROBOT: char = 'X';
+
+const GREEN: &str = "\x1b[32m";
+const RED: &str = "\x1b[31m";
+const BLUE: &str = "\x1b[34m";
+const BOLD_YELLOW: &str = "\x1b[1;33m";
+const RESET: &str = "\x1b[0m";
+
+/// How a maze drawn as text looks.
+#[derive(Args, Clone, Copy, Debug, Default)]
+pub struct Theme {
+    /// Draw walls w/ Unicode box-drawing lines instead of '+'
+    #[arg(long)]
+    pub boxes: bool,
+    /// Colour the start, finish, path & robot w/ ANSI escape codes
+    #[arg(long)]
+    pub colour: bool,
+}
+
+impl Theme {
+    /// Write the glyph for one cell, w/ the mark covering it if it is open.
+    fn draw(&self, out: &mut String, maze: &TextMaze, posn: (usize, usize), mark: Option<char>) {
+        let (glyph, colour) = match maze.cell_at(posn) {
+            _ if posn == maze.start() => ('S', Some(GREEN)),
+            Cell::Wall if self.boxes => (box_glyph(maze, posn), None),
+            Cell::Wall => ('+', None),
+            Cell::Finish => ('F', Some(RED)),
+            Cell::Open => match mark {
+                None => (' ', None),
+                Some(EXPLORED) => (EXPLORED, None),
+                Some(ROBOT) => (ROBOT, Some(BOLD_YELLOW)),
+                Some(glyph) => (glyph, Some(BLUE)),
+            },
+        };
+
+        match colour.filter(|_| self.colour) {
+            Some(code) => {
+                out.push_str(code);
+                out.push(glyph);
+                out.push_str(RESET);
+            }
+            None => out.push(glyph),
+        }
+    }
+}
+
+/// Box-drawing line for a wall, joining up w/ the walls next to it.
+fn box_glyph(maze: &TextMaze, posn: (usize, usize)) -> char {
+    let [north, east, south, west] = DIR_ARR.map(|dir| {
+        maze.step(posn, dir)
+            .is_some_and(|next| maze.cell_at(next) == Cell::Wall)
+    });
+
+    match (north, east, south, west) {
+        (true, true, true, true) => '┼',
+        (true, true, true, false) => '├',
+        (true, false, true, true) => '┤',
+        (false, true, true, true) => '┬',
+        (true, true, false, true) => '┴',
+        (false, true, true, false) => '┌',
+        (false, false, true, true) => '┐',
+        (true, true, false, false) => '└',
+        (true, false, false, true) => '┘',
+        (true, false, _, false) | (false, false, true, false) => '│',
+        _ => '─',
+    }
+}
+
+/// Grid position (column, row) of the given key, where keys are relative to the maze's start.
+///
+/// Keys grow north while rows grow south, so the vertical offset is flipped. Keys that land
+/// outside the grid have no position.
+pub fn grid_posn(maze: &TextMaze, key: &Key) -> Option<(usize, usize)> {
+    let (start_col, start_row) = maze.start();
+    let col = start_col.checked_add_signed(key.east())?;
+    let row = start_row.checked_add_signed(-key.north())?;
+
+    (col < maze.width() && row < maze.height()).then_some((col, row))
+}
+
+/// Draw the maze w/ the run's path & explored cells marked on it.
+///
+/// The start & finish keep their 'S' & 'F', path cells are drawn w/ the given glyphs & cells the
+/// robot searched w/out using are drawn as ':'.
+pub fn render_run(maze: &TextMaze, run: &Run, glyphs: PathGlyphs, theme: Theme) -> String {
+    let mut marks: HashMap<(usize, usize), char> = run
+        .visited()
+        .iter()
+        .filter_map(|key| grid_posn(maze, key))
+        .map(|posn| (posn, EXPLORED))
+        .collect();
+
+    for (idx, key) in run.path.iter().enumerate() {
+        let Some(posn) = grid_posn(maze, key) else {
+            continue;
+        };
+        let glyph = match glyphs {
+            PathGlyphs::Dots => '.',
+            PathGlyphs::Arrows => match run.path.get(idx + 1).and_then(|n| key.direction_to(n)) {
+                Some(Direction::North) => '^',
+                Some(Direction::East) => '>',
+                Some(Direction::South) => 'v',
+                Some(Direction::West) => '<',
+                None => '.',
+            },
+        };
+
+        marks.insert(posn, glyph);
+    }
+
+    render_marked(maze, &marks, theme)
+}
+
+/// Draw the maze cell by cell, using the given marks in place of any open cells they cover.
+pub fn render_marked(
+    maze: &TextMaze,
+    marks: &HashMap<(usize, usize), char>,
+    theme: Theme,
+) -> String {
+    let mut out = String::with_capacity((maze.width() + 1) * maze.height());
+
+    for row in 0..maze.height() {
+        if row > 0 {
+            out.push('\n');
+        }
+        for col in 0..maze.width() {
+            theme.draw(&mut out, maze, (col, row), marks.get(&(col, row)).copied());
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{solution::Solver, text_maze::TextRobot};
+
+    const MAZE: &str = "\
++++++++
++   + F
+S +++ +
++     +
++++++++";
+
+    fn render_solved(glyphs: PathGlyphs) -> String {
+        let maze = TextMaze::try_from(MAZE).expect("maze to create successfully");
+        let run = Solver::Dfs
+            .run(TextRobot::try_from(MAZE).expect("maze to create successfully"))
+            .expect("solution to be found");
+
+        render_run(&maze, &run, glyphs, Theme::default())
+    }
+
+    #[rstest]
+    fn test_grid_posn_flips_north() {
+        let maze = TextMaze::try_from(MAZE).expect("maze to create successfully");
+        let key = Key::default().compute_in_dir(&Direction::North);
+
+        assert_eq!(grid_posn(&maze, &key), Some((0, 1)));
+        assert_eq!(grid_posn(&maze, &Key::default()), Some((0, 2)));
+    }
+
+    #[rstest]
+    fn test_grid_posn_out_of_bounds() {
+        let maze = TextMaze::try_from(MAZE).expect("maze to create successfully");
+        let key = Key::default().compute_in_dir(&Direction::West);
+
+        assert_eq!(grid_posn(&maze, &key), None);
+    }
+
+    #[rstest]
+    #[case(
+        PathGlyphs::Arrows,
+        "\
++++++++
++:::+>F
+Sv+++^+
++>>>>^+
++++++++"
+    )]
+    #[case(
+        PathGlyphs::Dots,
+        "\
++++++++
++:::+.F
+S.+++.+
++.....+
++++++++"
+    )]
+    fn test_render_run(#[case] glyphs: PathGlyphs, #[case] exp: &str) {
+        assert_eq!(render_solved(glyphs), exp)
+    }
+
+    #[rstest]
+    fn test_boxes_read_back_as_walls() {
+        let maze = TextMaze::try_from(MAZE).expect("maze to create successfully");
+        let theme = Theme {
+            boxes: true,
+            ..Default::default()
+        };
+        let act = render_marked(&maze, &HashMap::new(), theme);
+
+        assert_eq!(
+            act,
+            "\
+┌───┬──
+│   │ F
+S ──┘ │
+│     │
+└─────┘"
+        );
+        assert_eq!(
+            render_marked(
+                &TextMaze::try_from(act.as_str()).expect("boxes to read as a maze"),
+                &HashMap::new(),
+                Theme::default()
+            ),
+            MAZE
+        );
+    }
+
+    #[rstest]
+    fn test_colour() {
+        let maze = TextMaze::try_from("S F").expect("maze to create successfully");
+        let theme = Theme {
+            colour: true,
+            ..Default::default()
+        };
+        let act = render_marked(&maze, &HashMap::from([((1, 0), ROBOT)]), theme);
+
+        assert_eq!(
+            act,
+            format!("{GREEN}S{RESET}{BOLD_YELLOW}X{RESET}{RED}F{RESET}")
+        );
+    }
+}
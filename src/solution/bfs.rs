@@ -0,0 +1,113 @@
+use std::collections::{HashSet, VecDeque};
+
+use anyhow::anyhow;
+
+use maze_robot::controller::{AsyncRobot, Cell, Robot};
+
+use crate::solution::{
+    Key, KnownMap, Run, Stats,
+    immediate::{Immediate, run_now},
+};
+
+/// Search breadth first, looking around every cell a step from the start, then every cell two
+/// steps away & so on, so the finish found is one of the nearest.
+///
+/// One robot can't stand at every cell along the edge of the search at once, so it drives to
+/// each in turn along the shortest route it knows, which makes for far more driving than
+/// depth first search.
+pub fn bfs_path<R: Robot>(robot: &R) -> anyhow::Result<Run> {
+    run_now(bfs_path_async(&Immediate(robot)))
+}
+
+/// Search breadth first like `bfs_path`, waiting on the robot's maze rather than blocking on it.
+pub async fn bfs_path_async<R: AsyncRobot>(robot: &R) -> anyhow::Result<Run> {
+    let mut map = KnownMap::new();
+    let mut key = Key::default();
+    let mut trail = vec![key];
+    let mut stood = HashSet::from([key]);
+    let mut queued = HashSet::from([key]);
+    let mut queue = VecDeque::from([(key, 1)]);
+    let mut stats = Stats::default();
+
+    while let Some((target, depth)) = queue.pop_front() {
+        let route = map
+            .route(key, |cell| *cell == target)
+            .ok_or(anyhow!("No known route to the next cell to search!"))?;
+        for next in &route[1..] {
+            go(robot, key, *next).await?;
+            key = *next;
+            trail.push(key);
+            if !stood.insert(key) {
+                stats.backtracks += 1;
+            }
+        }
+        stats.max_depth = stats.max_depth.max(depth);
+
+        for (cell, dir) in robot.peek_all().await {
+            map.look(key, dir, cell);
+            let next = key.compute_in_dir(&dir);
+            match cell {
+                Cell::Finish => {
+                    go(robot, key, next).await?;
+                    trail.push(next);
+                    stats.max_depth = stats.max_depth.max(depth + 1);
+                    let path = map
+                        .route(Key::default(), |cell| *cell == next)
+                        .ok_or(anyhow!("No known route to the finish!"))?;
+                    return Ok(Run { path, trail, stats });
+                }
+                Cell::Open if queued.insert(next) => queue.push_back((next, depth + 1)),
+                _ => (),
+            }
+        }
+    }
+
+    Err(anyhow!("No path to the finish was found!"))
+}
+
+/// Move the robot a single step from one cell to its neighbour.
+async fn go<R: AsyncRobot>(robot: &R, from: Key, to: Key) -> anyhow::Result<()> {
+    let Some(dir) = from.direction_to(&to) else {
+        return Ok(());
+    };
+
+    robot.go(dir).await.map_err(|e| {
+        anyhow::Error::from(e).context("Error encountered while searching breadth first.")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::text_maze::TextRobot;
+
+    #[rstest]
+    fn test_finds_nearest_finish() {
+        // dfs tries north first, so finds the finish the long way round
+        let robot = TextRobot::try_from("   \n + \nS F").expect("maze to create successfully");
+        let act = bfs_path(&robot).expect("solution to be found");
+
+        assert_eq!(act.path, vec![Key(0, 0), Key(1, 0), Key(2, 0)]);
+        assert_eq!(act.trail.last(), Some(&Key(2, 0)));
+    }
+
+    #[rstest]
+    fn test_searches_nearer_cells_first() {
+        // the dead end to the west is nearer than the finish, so is searched on the way
+        let robot = TextRobot::try_from("  S    F").expect("maze to create successfully");
+        let act = bfs_path(&robot).expect("solution to be found");
+
+        assert_eq!(act.path.len(), 6);
+        assert_eq!(act.visited().len(), 8);
+        assert_eq!(act.stats.max_depth, 6);
+    }
+
+    #[rstest]
+    fn test_fails_w_out_finish() {
+        let robot = TextRobot::try_from("S +\n+  ").expect("maze to create successfully");
+
+        assert!(bfs_path(&robot).is_err());
+    }
+}
@@ -0,0 +1,342 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+};
+
+use anyhow::anyhow;
+
+use maze_robot::controller::{AsyncRobot, Cell, DIR_ARR, Direction, Robot};
+
+use crate::solution::{
+    Key, KnownMap, Run, Stats, finish_keys,
+    immediate::{Immediate, run_now},
+};
+
+/// Cost of crossing a side known to be walled, or of a route that doesn't exist.
+const BLOCKED: usize = usize::MAX;
+
+/// Order cells are searched in, smallest first.
+type Priority = (usize, usize);
+
+/// Plan a route to a finish the maze has said the whereabouts of, assuming every side not yet
+/// seen is open, & repair the plan as the robot finds walls in the way.
+///
+/// This is D* Lite: the plan is searched backwards from the finish, so each repair only searches
+/// again the cells whose distance a new wall changed, rather than planning from scratch.
+pub fn d_star_lite_path<R: Robot>(robot: &R) -> anyhow::Result<Run> {
+    run_now(d_star_lite_path_async(&Immediate(robot)))
+}
+
+/// Plan a route to the finish like `d_star_lite_path`, waiting on the robot's maze rather than
+/// blocking on it.
+pub async fn d_star_lite_path_async<R: AsyncRobot>(robot: &R) -> anyhow::Result<Run> {
+    let goals = finish_keys(robot).await?;
+    let mut planner = Planner::new(&goals);
+    let mut last = planner.start;
+    let mut trail = vec![planner.start];
+    let mut stood = HashSet::from([planner.start]);
+    let mut stats = Stats::default();
+
+    planner.sense(robot.peek_all().await);
+    planner.plan();
+
+    while !goals.contains(&planner.start) {
+        let (dir, next) = planner
+            .next_step()
+            .ok_or(anyhow!("No path to the finish was found!"))?;
+        stats.max_depth = stats.max_depth.max(planner.g(&planner.start) + 1);
+
+        robot.go(dir).await.map_err(|e| {
+            anyhow::Error::from(e).context("Error encountered while following plan to finish.")
+        })?;
+        planner.start = next;
+        trail.push(next);
+        if !stood.insert(next) {
+            stats.backtracks += 1;
+        }
+
+        let changed = planner.sense(robot.peek_all().await);
+        if !changed.is_empty() {
+            // priorities already queued were worked out from where the robot was, so rather than
+            // redo them all, raise every priority from here on by how far the robot has come
+            planner.km += distance(&last, &planner.start);
+            last = planner.start;
+            changed.iter().for_each(|cell| planner.update(cell));
+            planner.plan();
+        }
+    }
+
+    let path = planner
+        .map
+        .route(Key::default(), |key| goals.contains(key))
+        .ok_or(anyhow!("No path to the finish was found!"))?;
+
+    Ok(Run { path, trail, stats })
+}
+
+/// Steps between two cells if nothing were in the way.
+fn distance(a: &Key, b: &Key) -> usize {
+    a.east().abs_diff(b.east()) + a.north().abs_diff(b.north())
+}
+
+/// The robot's plan, as steps from each cell searched to the nearest goal.
+///
+/// Cells are kept to one past everything seen, like flood fill, so a finish that can't be reached
+/// doesn't leave the search spreading out forever.
+struct Planner<'a> {
+    goals: &'a [Key],
+    map: KnownMap,
+    /// Smallest & largest (east, north) of the cells searched.
+    bounds: ((isize, isize), (isize, isize)),
+    start: Key,
+    /// How much every priority has grown since the search began.
+    km: usize,
+    /// Steps to a goal as last searched.
+    g: HashMap<Key, usize>,
+    /// Steps to a goal going by the neighbours' `g`, which differs from `g` while a cell is
+    /// waiting to be searched again.
+    rhs: HashMap<Key, usize>,
+    queue: BinaryHeap<Reverse<(Priority, (isize, isize))>>,
+    /// The current priority of every cell in `queue`, so outdated entries can be skipped.
+    queued: HashMap<Key, Priority>,
+}
+
+impl<'a> Planner<'a> {
+    fn new(goals: &'a [Key]) -> Self {
+        let mut planner = Self {
+            goals,
+            map: KnownMap::new(),
+            bounds: ((0, 0), (0, 0)),
+            start: Key::default(),
+            km: 0,
+            g: HashMap::new(),
+            rhs: HashMap::new(),
+            queue: BinaryHeap::new(),
+            queued: HashMap::new(),
+        };
+        planner.bounds = planner.seen_bounds();
+        for goal in goals {
+            planner.rhs.insert(*goal, 0);
+            planner.enqueue(*goal);
+        }
+        planner
+    }
+
+    fn g(&self, key: &Key) -> usize {
+        self.g.get(key).copied().unwrap_or(BLOCKED)
+    }
+
+    fn rhs(&self, key: &Key) -> usize {
+        self.rhs.get(key).copied().unwrap_or(BLOCKED)
+    }
+
+    fn priority(&self, key: &Key) -> Priority {
+        let best = self.g(key).min(self.rhs(key));
+
+        (
+            best.saturating_add(distance(&self.start, key))
+                .saturating_add(self.km),
+            best,
+        )
+    }
+
+    /// Cost of stepping from a cell across one of its sides.
+    fn cost(&self, key: &Key, direction: Direction) -> usize {
+        let next = key.compute_in_dir(&direction);
+        let walled = self.map.across(key, direction) == Some(Cell::Wall);
+
+        if !self.in_bounds(key) || !self.in_bounds(&next) || walled {
+            BLOCKED
+        } else {
+            1
+        }
+    }
+
+    /// Take in what the robot saw looking around, returning every cell whose steps to a goal
+    /// might have changed.
+    fn sense(&mut self, cells: [(Cell, Direction); 4]) -> Vec<Key> {
+        let mut changed = vec![];
+
+        for (cell, dir) in cells {
+            let walled = self.map.across(&self.start, dir) == Some(Cell::Wall);
+            self.map.look(self.start, dir, cell);
+            if cell == Cell::Wall && !walled {
+                changed.extend([self.start, self.start.compute_in_dir(&dir)]);
+            }
+        }
+
+        // cells past the old edge of the search can now be searched
+        let (old_min, old_max) = self.bounds;
+        let (min, max) = self.seen_bounds();
+        self.bounds = (min, max);
+        // the bounds only ever grow, so only the new columns & rows along each side are new
+        let columns = (min.0..old_min.0)
+            .chain(old_max.0 + 1..=max.0)
+            .flat_map(|east| (min.1..=max.1).map(move |north| Key::new(east, north)));
+        let rows = (old_min.0..=old_max.0).flat_map(|east| {
+            (min.1..old_min.1)
+                .chain(old_max.1 + 1..=max.1)
+                .map(move |north| Key::new(east, north))
+        });
+        changed.extend(columns.chain(rows));
+
+        changed
+    }
+
+    /// Everything seen & every goal, plus the unseen ring around them.
+    fn seen_bounds(&self) -> ((isize, isize), (isize, isize)) {
+        let (min, max) = self.map.bounds_with(self.goals);
+
+        ((min.0 - 1, min.1 - 1), (max.0 + 1, max.1 + 1))
+    }
+
+    fn in_bounds(&self, key: &Key) -> bool {
+        let (min, max) = self.bounds;
+
+        (min.0..=max.0).contains(&key.east()) && (min.1..=max.1).contains(&key.north())
+    }
+
+    /// Work out a cell's `rhs` again from its neighbours, queueing it if it no longer agrees
+    /// w/ its `g`.
+    fn update(&mut self, key: &Key) {
+        if !self.in_bounds(key) {
+            return;
+        }
+        if !self.goals.contains(key) {
+            let rhs = DIR_ARR
+                .into_iter()
+                .map(|dir| {
+                    self.cost(key, dir)
+                        .saturating_add(self.g(&key.compute_in_dir(&dir)))
+                })
+                .min()
+                .unwrap_or(BLOCKED);
+            self.rhs.insert(*key, rhs);
+        }
+
+        self.queued.remove(key);
+        if self.g(key) != self.rhs(key) {
+            self.enqueue(*key);
+        }
+    }
+
+    fn enqueue(&mut self, key: Key) {
+        let priority = self.priority(&key);
+
+        self.queued.insert(key, priority);
+        self.queue
+            .push(Reverse((priority, (key.east(), key.north()))));
+    }
+
+    /// The cell to search next & the priority it was queued w/, dropping outdated entries.
+    fn peek_queue(&mut self) -> Option<(Priority, Key)> {
+        while let Some(&Reverse((priority, (east, north)))) = self.queue.peek() {
+            let key = Key::new(east, north);
+            if self.queued.get(&key) == Some(&priority) {
+                return Some((priority, key));
+            }
+            self.queue.pop();
+        }
+        None
+    }
+
+    /// Search until the robot's cell has the right number of steps to a goal.
+    fn plan(&mut self) {
+        while let Some((old, key)) = self.peek_queue() {
+            let settled = self.rhs(&self.start) == self.g(&self.start);
+            if old >= self.priority(&self.start) && settled {
+                break;
+            }
+
+            self.queue.pop();
+            self.queued.remove(&key);
+            let new = self.priority(&key);
+
+            if old < new {
+                self.enqueue(key);
+            } else if self.g(&key) > self.rhs(&key) {
+                self.g.insert(key, self.rhs(&key));
+                for dir in DIR_ARR {
+                    self.update(&key.compute_in_dir(&dir));
+                }
+            } else {
+                self.g.insert(key, BLOCKED);
+                self.update(&key);
+                for dir in DIR_ARR {
+                    self.update(&key.compute_in_dir(&dir));
+                }
+            }
+        }
+    }
+
+    /// The open side of the robot's cell w/ the fewest steps to a goal past it.
+    fn next_step(&self) -> Option<(Direction, Key)> {
+        DIR_ARR
+            .into_iter()
+            .map(|dir| {
+                let next = self.start.compute_in_dir(&dir);
+                (
+                    self.cost(&self.start, dir).saturating_add(self.g(&next)),
+                    dir,
+                    next,
+                )
+            })
+            .filter(|&(steps, _, _)| steps != BLOCKED)
+            .min_by_key(|&(steps, _, _)| steps)
+            .map(|(_, dir, next)| (dir, next))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::solution::tests::known_finish_robot;
+
+    #[rstest]
+    fn test_heads_straight_for_finish() {
+        let robot = known_finish_robot("    \n    \nS  F");
+        let act = d_star_lite_path(&robot).expect("solution to be found");
+
+        assert_eq!(act.trail, vec![Key(0, 0), Key(1, 0), Key(2, 0), Key(3, 0)]);
+    }
+
+    #[rstest]
+    #[case("S+F\n   ", 5)]
+    #[case("   +   \n + + + \nS+   +F", 15)]
+    fn test_replans_around_walls(#[case] maze: &str, #[case] exp: usize) {
+        let robot = known_finish_robot(maze);
+        let act = d_star_lite_path(&robot).expect("solution to be found");
+
+        assert_eq!(act.path.len(), exp);
+        assert_eq!(act.trail.last(), act.path.last());
+    }
+
+    #[rstest]
+    fn test_senses_only_cells_past_old_bounds() {
+        let goals = [Key(1, 0)];
+        let mut planner = Planner::new(&goals);
+        let (old_min, old_max) = planner.bounds;
+
+        let mut act = planner.sense(known_finish_robot("   \n S \n   ").peek_all());
+        act.sort_by_key(|key| (key.east(), key.north()));
+
+        let (min, max) = planner.bounds;
+        let exp: Vec<Key> = (min.0..=max.0)
+            .flat_map(|east| (min.1..=max.1).map(move |north| Key(east, north)))
+            .filter(|key| {
+                !(old_min.0..=old_max.0).contains(&key.east())
+                    || !(old_min.1..=old_max.1).contains(&key.north())
+            })
+            .collect();
+        assert_eq!(act, exp);
+    }
+
+    #[rstest]
+    fn test_gives_up_on_walled_off_finish() {
+        let robot = known_finish_robot("S  +F\n+  ++");
+
+        assert!(d_star_lite_path(&robot).is_err());
+    }
+}
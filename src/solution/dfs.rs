@@ -0,0 +1,86 @@
+use std::{array, collections::HashSet};
+
+use anyhow::anyhow;
+
+use maze_robot::controller::{AsyncRobot, Cell, Direction, Robot};
+
+use crate::solution::{
+    Key, Run, Stats,
+    immediate::{Immediate, run_now},
+};
+
+/// Search depth first, keeping the cells between the start & the robot on an explicit stack
+/// rather than the call stack, so paths millions of cells long can't overflow it.
+pub fn dfs_path<R: Robot>(robot: &R) -> anyhow::Result<Run> {
+    run_now(dfs_path_async(&Immediate(robot)))
+}
+
+/// Search depth first like `dfs_path`, waiting on the robot's maze rather than blocking on it, so
+/// other robots can search while this one waits.
+pub async fn dfs_path_async<R: AsyncRobot>(robot: &R) -> anyhow::Result<Run> {
+    let mut visited = HashSet::new();
+    let mut trail = vec![];
+    let mut stack: Vec<Frame> = vec![];
+    let mut next = Some((Key::default(), Cell::Open, None));
+    let mut stats = Stats::default();
+
+    loop {
+        // enter a new cell, moving the robot into it if this isn't the start
+        if let Some((key, cell, direction)) = next.take() {
+            if let Some(dir) = direction {
+                robot.go(dir).await.map_err(|e| search_error(e.into()))?;
+            }
+            trail.push(key);
+
+            if let Cell::Finish = cell {
+                let path = stack.iter().map(|frame| frame.key).chain([key]).collect();
+                stats.max_depth = stats.max_depth.max(stack.len() + 1);
+                return Ok(Run { path, trail, stats });
+            }
+
+            visited.insert(key);
+            stack.push(Frame {
+                key,
+                entered_by: direction,
+                // peek in each direction once, on entering
+                neighbours: robot.peek_all().await.into_iter(),
+            });
+            stats.max_depth = stats.max_depth.max(stack.len());
+        }
+
+        let Some(frame) = stack.last_mut() else {
+            return Err(anyhow!("No path to the finish was found!"));
+        };
+
+        match frame.neighbours.next() {
+            // skip walls & cells already searched
+            Some((Cell::Wall, _)) => (),
+            Some((_, dir)) if visited.contains(&frame.key.compute_in_dir(&dir)) => (),
+            Some((cell, dir)) => next = Some((frame.key.compute_in_dir(&dir), cell, Some(dir))),
+            // every neighbour searched, so back out the way the robot came in
+            None => {
+                let entered_by = frame.entered_by;
+                stack.pop();
+                if let (Some(dir), Some(parent)) = (entered_by, stack.last()) {
+                    robot
+                        .go(dir.reverse())
+                        .await
+                        .map_err(|e| search_error(e.into()))?;
+                    trail.push(parent.key);
+                    stats.backtracks += 1;
+                }
+            }
+        }
+    }
+}
+
+fn search_error(e: anyhow::Error) -> anyhow::Error {
+    e.context("Error encountered while searching for finish.")
+}
+
+/// A cell on the way from the start to the robot, & the neighbours it has left to search.
+struct Frame {
+    key: Key,
+    entered_by: Option<Direction>,
+    neighbours: array::IntoIter<(Cell, Direction), 4>,
+}
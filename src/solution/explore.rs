@@ -0,0 +1,109 @@
+use std::collections::HashSet;
+
+use anyhow::anyhow;
+
+use maze_robot::controller::{AsyncRobot, DIR_ARR, Robot};
+
+use crate::solution::{
+    Key, KnownMap, Run, Stats,
+    immediate::{Immediate, run_now},
+};
+
+/// Sense every cell reachable from the start, then give the shortest path to the finish on the
+/// completed map.
+///
+/// The robot always heads for the nearest cell it has seen but not yet looked around, which
+/// keeps the distance it travels between unexplored areas short. Once everything is explored it
+/// drives to the finish, so like every other solver it ends the run standing on it.
+pub fn explore_path<R: Robot>(robot: &R) -> anyhow::Result<Run> {
+    run_now(explore_path_async(&Immediate(robot)))
+}
+
+/// Explore the whole maze like `explore_path`, waiting on the robot's maze rather than blocking
+/// on it.
+pub async fn explore_path_async<R: AsyncRobot>(robot: &R) -> anyhow::Result<Run> {
+    let mut map = KnownMap::new();
+    let mut key = Key::default();
+    let mut trail = vec![key];
+    let mut stood = HashSet::from([key]);
+    let mut stats = Stats::default();
+
+    loop {
+        // only look across sides not already seen from the other side
+        for dir in DIR_ARR {
+            if !map.looked(&key, dir) {
+                map.look(key, dir, robot.peek(dir).await);
+            }
+        }
+
+        let route = match map.route(key, |cell| !map.looked_around(cell)) {
+            Some(route) => route,
+            None => match map.shortest_path().and_then(|path| path.last().copied()) {
+                Some(finish) if finish != key => map
+                    .route(key, |cell| *cell == finish)
+                    .ok_or(anyhow!("No known route to the finish!"))?,
+                _ => break,
+            },
+        };
+        stats.max_depth = stats.max_depth.max(route.len());
+
+        for next in &route[1..] {
+            if let Some(dir) = key.direction_to(next) {
+                robot.go(dir).await.map_err(|e| {
+                    anyhow::Error::from(e).context("Error encountered while exploring.")
+                })?;
+            }
+            key = *next;
+            trail.push(key);
+            if !stood.insert(key) {
+                stats.backtracks += 1;
+            }
+        }
+    }
+
+    let path = map.shortest_path().ok_or(anyhow!(
+        "Every reachable cell was explored w/out finding the finish!"
+    ))?;
+
+    Ok(Run { path, trail, stats })
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{solution::MappingRobot, text_maze::TextRobot};
+
+    #[rstest]
+    fn test_senses_every_reachable_cell() {
+        // the dead end in the bottom left is off the way to the finish, but still explored
+        let robot = MappingRobot::new(
+            TextRobot::try_from("S F\n+ +\n  +").expect("maze to create successfully"),
+        );
+        let act = explore_path(&robot).expect("maze to be explored");
+
+        assert_eq!(act.path, vec![Key(0, 0), Key(1, 0), Key(2, 0)]);
+        assert_eq!(act.visited().len(), 6);
+        // back out of the dead end to finish on the finish
+        assert_eq!(act.trail.last(), Some(&Key(2, 0)));
+        assert_eq!(robot.position(), Key(2, 0));
+        assert_eq!(robot.map().to_string(), "?+++?\n+S F+\n?+ +?\n+  +?\n?++??");
+    }
+
+    #[rstest]
+    fn test_finds_shortest_path_after_exploring() {
+        // the robot tries north first, so finds the finish the long way round
+        let robot = TextRobot::try_from("   \n + \nS F").expect("maze to create successfully");
+        let act = explore_path(&robot).expect("maze to be explored");
+
+        assert_eq!(act.path, vec![Key(0, 0), Key(1, 0), Key(2, 0)]);
+    }
+
+    #[rstest]
+    fn test_fails_w_out_finish() {
+        let robot = TextRobot::try_from("S +\n+  ").expect("maze to create successfully");
+
+        assert!(explore_path(&robot).is_err());
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::{HashSet, VecDeque};
+
+use anyhow::anyhow;
+
+use maze_robot::controller::{AsyncRobot, Cell, DIR_ARR, Direction, Robot};
+
+use crate::solution::{
+    Key, KnownMap, Run, Stats, finish_keys,
+    immediate::{Immediate, run_now},
+};
+
+/// Head for a finish the maze has said the whereabouts of, always stepping to the neighbour
+/// fewest steps from it.
+///
+/// Steps are counted as if every side not yet seen is open, & counted again whenever the walls
+/// seen leave no neighbour closer, which is the modified flood fill micromouse robots use.
+pub fn flood_fill_path<R: Robot>(robot: &R) -> anyhow::Result<Run> {
+    run_now(flood_fill_path_async(&Immediate(robot)))
+}
+
+/// Flood fill to the finish like `flood_fill_path`, waiting on the robot's maze rather than
+/// blocking on it.
+pub async fn flood_fill_path_async<R: AsyncRobot>(robot: &R) -> anyhow::Result<Run> {
+    let goals = finish_keys(robot).await?;
+    let mut map = KnownMap::new();
+    let mut key = Key::default();
+    let mut trail = vec![key];
+    let mut stood = HashSet::from([key]);
+    let mut stats = Stats::default();
+    let mut distances = flood(&map, &goals, key);
+
+    while !goals.contains(&key) {
+        for dir in DIR_ARR {
+            if !map.looked(&key, dir) {
+                map.look(key, dir, robot.peek(dir).await);
+            }
+        }
+
+        // counts only ever grow as walls turn up, so only flood again once they lead nowhere
+        let (dir, next) = match downhill(&map, &distances, key) {
+            Some(step) => step,
+            None => {
+                distances = flood(&map, &goals, key);
+                downhill(&map, &distances, key)
+                    .ok_or(anyhow!("No path to the finish was found!"))?
+            }
+        };
+        stats.max_depth = stats
+            .max_depth
+            .max(distances.get(&key).unwrap_or_default() + 1);
+
+        robot.go(dir).await.map_err(|e| {
+            anyhow::Error::from(e).context("Error encountered while flooding to finish.")
+        })?;
+        key = next;
+        trail.push(key);
+        if !stood.insert(key) {
+            stats.backtracks += 1;
+        }
+    }
+
+    let path = map
+        .route(Key::default(), |key| goals.contains(key))
+        .ok_or(anyhow!("No path to the finish was found!"))?;
+
+    Ok(Run { path, trail, stats })
+}
+
+/// Step through an open side to a neighbour fewer steps from the finish than the given cell.
+fn downhill(map: &KnownMap, distances: &Distances, key: Key) -> Option<(Direction, Key)> {
+    let here = distances.get(&key)?;
+
+    DIR_ARR
+        .into_iter()
+        .filter(|&dir| matches!(map.across(&key, dir), Some(Cell::Open | Cell::Finish)))
+        .map(|dir| (dir, key.compute_in_dir(&dir)))
+        .find(|(_, next)| distances.get(next).is_some_and(|dist| dist < here))
+}
+
+/// Steps from each cell to the nearest goal, assuming every side not yet seen is open.
+///
+/// Only cells up to one past everything seen are flooded. The unseen ring that leaves around the
+/// map is always joined up, so any way round through unseen cells further out goes through it.
+///
+/// Flooding stops once it reaches the robot, since every cell counted by then has a neighbour
+/// counted one step closer, all the way down to a goal.
+fn flood(map: &KnownMap, goals: &[Key], robot: Key) -> Distances {
+    let (min, max) = map.bounds_with(goals);
+    let mut distances = Distances {
+        west: min.0 - 1,
+        south: min.1 - 1,
+        width: (max.0 - min.0 + 3) as usize,
+        steps: vec![None; ((max.0 - min.0 + 3) * (max.1 - min.1 + 3)) as usize],
+    };
+    let mut queue = VecDeque::new();
+    for &goal in goals {
+        distances.set(&goal, 0);
+        queue.push_back((goal, 0));
+    }
+
+    while let Some((key, dist)) = queue.pop_front() {
+        if key == robot {
+            break;
+        }
+
+        for dir in DIR_ARR {
+            let next = key.compute_in_dir(&dir);
+            let blocked = map.across(&key, dir) == Some(Cell::Wall);
+            if !blocked && distances.set(&next, dist + 1) {
+                queue.push_back((next, dist + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// Steps to the nearest goal for every cell in a rectangle, packed in rows from the south west.
+struct Distances {
+    west: isize,
+    south: isize,
+    width: usize,
+    steps: Vec<Option<usize>>,
+}
+
+impl Distances {
+    fn get(&self, key: &Key) -> Option<usize> {
+        self.idx(key).and_then(|idx| self.steps[idx])
+    }
+
+    /// Count the steps for a cell if it is inside the rectangle & hasn't been counted yet.
+    fn set(&mut self, key: &Key, dist: usize) -> bool {
+        match self.idx(key).map(|idx| &mut self.steps[idx]) {
+            Some(steps @ None) => {
+                *steps = Some(dist);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn idx(&self, key: &Key) -> Option<usize> {
+        let col = usize::try_from(key.east() - self.west).ok()?;
+        let row = usize::try_from(key.north() - self.south).ok()?;
+
+        (col < self.width && row < self.steps.len() / self.width).then(|| row * self.width + col)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use maze_robot::controller::RobotInternal;
+
+    use super::*;
+    use crate::{record::ReplayMaze, solution::tests::known_finish_robot, text_maze::TextRobot};
+
+    #[rstest]
+    fn test_heads_straight_for_finish() {
+        // dfs would try the open area to the north first
+        let robot = known_finish_robot("    \n    \nS  F");
+        let act = flood_fill_path(&robot).expect("solution to be found");
+
+        assert_eq!(act.trail, vec![Key(0, 0), Key(1, 0), Key(2, 0), Key(3, 0)]);
+        assert_eq!(act.path, act.trail);
+    }
+
+    #[rstest]
+    fn test_floods_again_around_walls() {
+        let robot = known_finish_robot("     \nS+ +F\n +   ");
+        let act = flood_fill_path(&robot).expect("solution to be found");
+
+        assert_eq!(act.path.len(), 7);
+        assert_eq!(act.path.last(), Some(&Key(4, 0)));
+    }
+
+    #[rstest]
+    fn test_needs_to_know_where_finish_is() {
+        let robot = RobotInternal::new(ReplayMaze::new(vec![]));
+
+        assert!(flood_fill_path(&robot).is_err());
+    }
+
+    #[rstest]
+    fn test_finish_is_hidden_unless_asked_for() {
+        let robot = TextRobot::try_from("S F").expect("maze to create successfully");
+
+        assert!(flood_fill_path(&robot).is_err());
+    }
+}
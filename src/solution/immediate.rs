@@ -0,0 +1,44 @@
+use std::{
+    future::ready,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use anyhow::anyhow;
+
+use maze_robot::controller::{AsyncRobot, Cell, Direction, MazeError, Robot};
+
+use crate::solution::Run;
+
+/// A robot that answers straight away, so an async search can drive it w/out an executor.
+pub struct Immediate<'a, R: Robot>(pub &'a R);
+
+impl<R: Robot> AsyncRobot for Immediate<'_, R> {
+    fn peek(&self, direction: Direction) -> impl Future<Output = Cell> {
+        ready(self.0.peek(direction))
+    }
+
+    // robots like `MappingRobot` do more than peek each way when peeking all round
+    fn peek_all(&self) -> impl Future<Output = [(Cell, Direction); 4]> {
+        ready(self.0.peek_all())
+    }
+
+    fn go(&self, direction: Direction) -> impl Future<Output = Result<(), MazeError>> {
+        ready(self.0.go(direction))
+    }
+
+    fn finish_offsets(&self) -> impl Future<Output = Option<Vec<(isize, isize)>>> {
+        ready(self.0.get_internal().finish_offsets())
+    }
+}
+
+/// Run a search driving an `Immediate` robot, which never keeps it waiting, so one poll runs it
+/// to the end.
+pub fn run_now(search: impl Future<Output = anyhow::Result<Run>>) -> anyhow::Result<Run> {
+    match pin!(search).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(result) => result,
+        Poll::Pending => Err(anyhow!(
+            "Search waited on a robot that answers straight away!"
+        )),
+    }
+}
@@ -0,0 +1,431 @@
+use std::{
+    cell::{Cell as Slot, RefCell},
+    collections::{HashMap, VecDeque},
+    fmt::Display,
+    str::FromStr,
+};
+
+use maze_robot::controller::{
+    Cell, DIR_ARR, Direction, MazeError, Robot, RobotInternal, SourceLocation,
+};
+
+use crate::solution::Key;
+
+/// Glyph for cells the robot has never seen.
+pub const UNKNOWN: char = '?';
+
+/// Legend a saved map starts w/, so a maze read from it walls off cells the robot never saw
+/// rather than taking them to be open like any other character.
+const LEGEND: &str = "legend: +=wall ?=wall F=finish S=start";
+
+/// Everything a robot has sensed about its maze, keyed by location relative to the start.
+///
+/// Looks are kept per side of a cell as well as per cell, since in mazes w/ walls along cell
+/// edges a cell can be walled off on one side & open on another.
+#[derive(Clone, Debug, PartialEq)]
+pub struct KnownMap {
+    /// The most open each cell has looked from any side, cells w/out an entry never seen.
+    cells: HashMap<Key, Cell>,
+    /// What the robot saw looking out of a cell in each direction.
+    looks: HashMap<(Key, Direction), Cell>,
+}
+
+impl KnownMap {
+    /// A map w/ nothing seen but the start, which the robot is standing in.
+    pub fn new() -> Self {
+        Self {
+            cells: HashMap::from([(Key::default(), Cell::Open)]),
+            looks: HashMap::new(),
+        }
+    }
+
+    /// What the robot saw at the given location, if it has seen it.
+    pub fn get(&self, key: &Key) -> Option<Cell> {
+        self.cells.get(key).copied()
+    }
+
+    /// Note what the robot saw looking out of a cell in a direction.
+    pub fn look(&mut self, from: Key, direction: Direction, cell: Cell) {
+        self.looks.insert((from, direction), cell);
+
+        let seen = self
+            .cells
+            .entry(from.compute_in_dir(&direction))
+            .or_insert(cell);
+        *seen = most_specific(*seen, cell);
+    }
+
+    /// Whether the robot has looked across the given side of a cell, from either side of it.
+    pub fn looked(&self, key: &Key, direction: Direction) -> bool {
+        self.across(key, direction).is_some()
+    }
+
+    /// Whether the robot has looked across every side of a cell.
+    pub fn looked_around(&self, key: &Key) -> bool {
+        DIR_ARR.iter().all(|&dir| self.looked(key, dir))
+    }
+
+    /// What was seen across a side of a cell, looking from whichever side it was seen from.
+    pub fn across(&self, key: &Key, direction: Direction) -> Option<Cell> {
+        let from_here = self.looks.get(&(*key, direction));
+        // a side seen open from the other side shows whatever that side was seen to be
+        let next = key.compute_in_dir(&direction);
+        let from_there = || {
+            self.looks
+                .get(&(next, direction.reverse()))
+                .map(|&cell| match cell {
+                    Cell::Wall => Cell::Wall,
+                    _ => self.get(&next).unwrap_or(Cell::Open),
+                })
+        };
+
+        from_here.copied().or_else(from_there)
+    }
+
+    /// Fewest steps from the start to a finish the robot has seen, only crossing sides it has
+    /// seen to be open.
+    pub fn shortest_path(&self) -> Option<Vec<Key>> {
+        self.route(Key::default(), |key| self.get(key) == Some(Cell::Finish))
+    }
+
+    /// Fewest steps from one cell to the nearest cell meeting the goal, only crossing sides seen
+    /// to be open.
+    pub fn route(&self, from: Key, goal: impl Fn(&Key) -> bool) -> Option<Vec<Key>> {
+        let mut came_from = HashMap::from([(from, from)]);
+        let mut queue = VecDeque::from([from]);
+
+        while let Some(key) = queue.pop_front() {
+            if goal(&key) {
+                let mut path = vec![key];
+                let mut at = key;
+                while at != from {
+                    at = came_from[&at];
+                    path.push(at);
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            for dir in DIR_ARR {
+                let next = key.compute_in_dir(&dir);
+                let passable = matches!(self.across(&key, dir), Some(Cell::Open | Cell::Finish));
+                if passable && !came_from.contains_key(&next) {
+                    came_from.insert(next, key);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// Smallest & largest (east, north) of every cell seen, so the start when nothing is.
+    pub fn bounds(&self) -> ((isize, isize), (isize, isize)) {
+        self.bounds_with(&[])
+    }
+
+    /// Smallest & largest (east, north) of every cell seen & every one of `keys`, such as goals
+    /// the robot hasn't seen yet.
+    pub fn bounds_with(&self, keys: &[Key]) -> ((isize, isize), (isize, isize)) {
+        let keys = self.cells.keys().chain(keys);
+
+        keys.fold(((0, 0), (0, 0)), |(min, max), key| {
+            (
+                (min.0.min(key.east()), min.1.min(key.north())),
+                (max.0.max(key.east()), max.1.max(key.north())),
+            )
+        })
+    }
+
+    /// The map as a text maze file, w/ a legend making unseen cells walls.
+    ///
+    /// Text mazes only have walls that fill whole cells, so a map w/ a wall seen between two open
+    /// cells, as in mazes w/ walls along cell edges, can't be written as one.
+    pub fn to_maze(&self) -> Result<String, MazeError> {
+        let thin_wall = self.looks.iter().find(|&(&(from, dir), &cell)| {
+            let open = |key| matches!(self.get(&key), Some(Cell::Open | Cell::Finish));
+            cell == Cell::Wall && open(from) && open(from.compute_in_dir(&dir))
+        });
+
+        match thin_wall {
+            Some((&(from, dir), _)) => Err(MazeError::CreationError(format!(
+                "map has a wall along the {dir} edge of {from}, which a text maze can't show"
+            ))),
+            None => Ok(format!("{LEGEND}\n{self}\n")),
+        }
+    }
+
+    fn glyph(&self, key: &Key) -> char {
+        match self.get(key) {
+            _ if *key == Key::default() => 'S',
+            Some(Cell::Wall) => '+',
+            Some(Cell::Open) => ' ',
+            Some(Cell::Finish) => 'F',
+            None => UNKNOWN,
+        }
+    }
+}
+
+/// What a cell seen as both `a` & `b` is, keeping whichever says the most: a finish is also open
+/// to a robot that only moved into it, & a side walled from one cell can be open from another.
+pub fn most_specific(a: Cell, b: Cell) -> Cell {
+    match (a, b) {
+        (Cell::Finish, _) | (_, Cell::Finish) => Cell::Finish,
+        (Cell::Open, _) | (_, Cell::Open) => Cell::Open,
+        _ => Cell::Wall,
+    }
+}
+
+/// Draws the map in the text maze format, w/ '?' for cells the robot never saw.
+impl Display for KnownMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (min, max) = self.bounds();
+
+        for north in (min.1..=max.1).rev() {
+            let line: String = (min.0..=max.0)
+                .map(|east| self.glyph(&Key::new(east, north)))
+                .collect();
+            write!(f, "{line}")?;
+            if north != min.1 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Reads a map back from the text written by its `Display` or `to_maze`.
+impl FromStr for KnownMap {
+    type Err = MazeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut start = None;
+        let mut glyphs = vec![];
+        // the legend is only there for reading the map as a maze, & maps always use the same one
+        let (body, first_line) = match s.strip_prefix("legend:") {
+            Some(rest) => (rest.split_once('\n').map_or("", |(_, body)| body), 2),
+            None => (s, 1),
+        };
+
+        for (row, line) in body.lines().enumerate() {
+            for (col, chr) in line.chars().enumerate() {
+                let at = SourceLocation {
+                    line: row + first_line,
+                    column: col + 1,
+                };
+                let cell = match chr {
+                    'S' => match start.replace((row, col, at)) {
+                        Some((_, _, first)) => {
+                            return Err(MazeError::MultipleStarts { first, second: at });
+                        }
+                        None => Some(Cell::Open),
+                    },
+                    '+' => Some(Cell::Wall),
+                    ' ' => Some(Cell::Open),
+                    'F' => Some(Cell::Finish),
+                    UNKNOWN => None,
+                    _ => {
+                        return Err(MazeError::CreationError(format!(
+                            "map has an unexpected '{chr}' at {at}"
+                        )));
+                    }
+                };
+                glyphs.push((row, col, cell));
+            }
+        }
+
+        let (start_row, start_col, _) = start.ok_or(MazeError::MissingStart)?;
+        let cells: HashMap<Key, Cell> = glyphs
+            .into_iter()
+            .filter_map(|(row, col, cell)| {
+                let key = Key::new(
+                    col as isize - start_col as isize,
+                    start_row as isize - row as isize,
+                );
+                cell.map(|cell| (key, cell))
+            })
+            .collect();
+
+        // the text only has cells, so treat every open cell as having looked at its neighbours
+        let looks = cells
+            .iter()
+            .filter(|&(_, cell)| cell != &Cell::Wall)
+            .flat_map(|(key, _)| DIR_ARR.map(|dir| (*key, dir)))
+            .filter_map(|(key, dir)| {
+                let seen = cells.get(&key.compute_in_dir(&dir))?;
+                Some(((key, dir), *seen))
+            })
+            .collect();
+
+        Ok(Self { cells, looks })
+    }
+}
+
+/// A robot that keeps a map of every cell it has looked at.
+pub struct MappingRobot<R: Robot> {
+    robot: R,
+    key: Slot<Key>,
+    map: RefCell<KnownMap>,
+}
+
+impl<R: Robot> MappingRobot<R> {
+    pub fn new(robot: R) -> Self {
+        Self {
+            robot,
+            key: Slot::new(Key::default()),
+            map: RefCell::new(KnownMap::new()),
+        }
+    }
+
+    /// Where the robot is relative to the start.
+    pub fn position(&self) -> Key {
+        self.key.get()
+    }
+
+    /// What the robot has seen so far.
+    pub fn map(&self) -> KnownMap {
+        self.map.borrow().clone()
+    }
+
+    fn saw(&self, direction: Direction, cell: Cell) {
+        self.map.borrow_mut().look(self.key.get(), direction, cell);
+    }
+}
+
+impl<R: Robot> Robot for MappingRobot<R> {
+    fn get_internal(&self) -> &RobotInternal {
+        self.robot.get_internal()
+    }
+
+    fn peek(&self, direction: Direction) -> Cell {
+        let cell = self.robot.peek(direction);
+        self.saw(direction, cell);
+        cell
+    }
+
+    fn peek_all(&self) -> [(Cell, Direction); 4] {
+        let cells = self.robot.peek_all();
+        cells.iter().for_each(|&(cell, dir)| self.saw(dir, cell));
+        cells
+    }
+
+    fn go(&self, direction: Direction) -> Result<(), MazeError> {
+        self.robot.go(direction)?;
+        self.key.set(self.key.get().compute_in_dir(&direction));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{
+        solution::Solver,
+        text_maze::{TextMaze, TextRobot},
+    };
+
+    fn mapped(maze: &str) -> KnownMap {
+        let robot = MappingRobot::new(TextRobot::try_from(maze).expect("maze to create"));
+        Solver::Dfs.run(&robot).expect("solution to be found");
+
+        robot.map()
+    }
+
+    #[rstest]
+    fn test_maps_every_peek() {
+        let act = mapped(" ++\nS F\n+ +");
+
+        assert_eq!(act.to_string(), "?+??\n+ +?\n+S F\n?+ ?");
+        assert_eq!(act.get(&Key::new(1, -1)), Some(Cell::Open));
+        assert_eq!(act.get(&Key::new(1, -2)), None);
+    }
+
+    #[rstest]
+    fn test_reads_back_what_it_draws() {
+        let map = mapped(" ++\nS F\n+ +");
+        let act: KnownMap = map.to_string().parse().expect("map to parse");
+
+        assert_eq!(act.to_string(), map.to_string());
+        assert_eq!(act.shortest_path(), map.shortest_path());
+    }
+
+    #[rstest]
+    fn test_saved_map_reads_back_as_maze_w_unseen_cells_walled() {
+        let map = mapped(" ++\nS F\n+ +");
+        let text = map.to_maze().expect("map to be saved");
+        let maze = TextMaze::try_from(text.as_str()).expect("maze to create");
+
+        assert_eq!(maze.to_string(), "++++\n+ ++\n+X F\n++ +");
+        let act: KnownMap = text.parse().expect("map to parse");
+        assert_eq!(act.to_string(), map.to_string());
+    }
+
+    #[rstest]
+    #[case("?+\n+ \n  ", MazeError::MissingStart)]
+    #[case(
+        "S S",
+        MazeError::MultipleStarts {
+            first: SourceLocation { line: 1, column: 1 },
+            second: SourceLocation { line: 1, column: 3 },
+        }
+    )]
+    #[case(
+        "legend: +=wall ?=wall F=finish S=start\nS\nS",
+        MazeError::MultipleStarts {
+            first: SourceLocation { line: 2, column: 1 },
+            second: SourceLocation { line: 3, column: 1 },
+        }
+    )]
+    fn test_rejects_bad_maps(#[case] map: &str, #[case] exp: MazeError) {
+        assert_eq!(map.parse::<KnownMap>(), Err(exp));
+    }
+
+    #[rstest]
+    #[case("S+F", None)]
+    #[case("S F", Some(vec![Key::new(0, 0), Key::new(1, 0), Key::new(2, 0)]))]
+    // the finish to the north is closer than the one to the east
+    #[case(" F\n ?\nS   F", Some(vec![Key::new(0, 0), Key::new(0, 1), Key::new(0, 2), Key::new(1, 2)]))]
+    fn test_shortest_known_path(#[case] map: &str, #[case] exp: Option<Vec<Key>>) {
+        let map: KnownMap = map.parse().expect("map to parse");
+
+        assert_eq!(map.shortest_path(), exp);
+    }
+
+    #[rstest]
+    fn test_walled_side_doesnt_wall_off_cell() {
+        let mut map = KnownMap::new();
+        map.look(Key::new(0, 0), Direction::East, Cell::Wall);
+        map.look(Key::new(0, 0), Direction::North, Cell::Open);
+        map.look(Key::new(0, 1), Direction::East, Cell::Open);
+        map.look(Key::new(1, 1), Direction::South, Cell::Open);
+
+        assert_eq!(map.get(&Key::new(1, 0)), Some(Cell::Open));
+        assert!(!map.looked(&Key::new(1, 0), Direction::East));
+        assert_eq!(
+            map.route(Key::default(), |key| *key == Key::new(1, 0)),
+            Some(vec![
+                Key::new(0, 0),
+                Key::new(0, 1),
+                Key::new(1, 1),
+                Key::new(1, 0)
+            ])
+        );
+        assert_eq!(
+            map.to_maze(),
+            Err(MazeError::CreationError(String::from(
+                "map has a wall along the East edge of (0,0), which a text maze can't show"
+            )))
+        );
+    }
+
+    #[rstest]
+    fn test_failed_move_leaves_robot_in_place() {
+        let robot = MappingRobot::new(TextRobot::try_from("S+F").expect("maze to create"));
+
+        assert!(robot.go(Direction::East).is_err());
+        robot.peek(Direction::West);
+
+        assert_eq!(robot.map().to_string(), "+S");
+    }
+}
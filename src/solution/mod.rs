@@ -0,0 +1,541 @@
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt::Display,
+    time::Instant,
+};
+
+use anyhow::anyhow;
+use clap::ValueEnum;
+use serde::Serialize;
+
+use maze_robot::controller::{AsyncRobot, Direction, Robot};
+
+mod bfs;
+mod d_star_lite;
+mod dfs;
+mod explore;
+mod flood;
+mod immediate;
+mod map;
+mod stats;
+mod wall_follower;
+
+use bfs::{bfs_path, bfs_path_async};
+use d_star_lite::{d_star_lite_path, d_star_lite_path_async};
+use dfs::{dfs_path, dfs_path_async};
+use explore::{explore_path, explore_path_async};
+use flood::{flood_fill_path, flood_fill_path_async};
+pub use map::{KnownMap, MappingRobot, most_specific};
+use stats::CountingRobot;
+pub use stats::Stats;
+use wall_follower::{wall_follower_path, wall_follower_path_async};
+
+/// Strategies a robot can use to find its way to the finish.
+#[derive(Clone, Copy, Debug, Default, Serialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Solver {
+    /// Depth first search, backing out of dead ends
+    #[default]
+    Dfs,
+    /// Breadth first search, driving to the nearest unsearched cell each time
+    Bfs,
+    /// Keep the left hand on a wall until the finish turns up
+    WallFollower,
+    /// Sense every reachable cell, then take the shortest path found to the finish
+    Explore,
+    /// Micromouse flood fill, for mazes that say where the finish is
+    FloodFill,
+    /// Plan through unseen cells & repair the plan as walls turn up, for mazes that say where
+    /// the finish is
+    DStarLite,
+}
+
+impl Solver {
+    /// Search for the finish w/ a robot that has already been placed in its maze.
+    ///
+    /// A search that gives up still says how much work it did before it did.
+    pub fn run<R: Robot>(self, robot: R) -> Result<Run, Failed> {
+        let robot = CountingRobot::new(robot);
+        let begin = Instant::now();
+        let result = match self {
+            Self::Dfs => dfs_path(&robot),
+            Self::Bfs => bfs_path(&robot),
+            Self::WallFollower => wall_follower_path(&robot),
+            Self::Explore => explore_path(&robot),
+            Self::FloodFill => flood_fill_path(&robot),
+            Self::DStarLite => d_star_lite_path(&robot),
+        };
+        let stats = Stats {
+            peeks: robot.peeks(),
+            moves: robot.moves(),
+            elapsed: begin.elapsed(),
+            ..Default::default()
+        };
+
+        match result {
+            Ok(mut run) => {
+                run.stats = Stats {
+                    cells_visited: run.visited().len(),
+                    backtracks: run.stats.backtracks,
+                    max_depth: run.stats.max_depth,
+                    ..stats
+                };
+                Ok(run)
+            }
+            Err(error) => Err(Failed { stats, error }),
+        }
+    }
+
+    /// Search w/ a robot in an `AsyncMaze`, counting its work like `Solver::run`.
+    ///
+    /// Each search only makes progress while it is polled, so many can be joined & run at once on
+    /// a single executor.
+    pub async fn run_async<R: AsyncRobot>(self, robot: R) -> anyhow::Result<Run> {
+        let robot = CountingRobot::new(robot);
+        let begin = Instant::now();
+        let mut run = match self {
+            Self::Dfs => dfs_path_async(&robot).await,
+            Self::Bfs => bfs_path_async(&robot).await,
+            Self::WallFollower => wall_follower_path_async(&robot).await,
+            Self::Explore => explore_path_async(&robot).await,
+            Self::FloodFill => flood_fill_path_async(&robot).await,
+            Self::DStarLite => d_star_lite_path_async(&robot).await,
+        }?;
+
+        run.stats = Stats {
+            peeks: robot.peeks(),
+            moves: robot.moves(),
+            cells_visited: run.visited().len(),
+            elapsed: begin.elapsed(),
+            ..run.stats
+        };
+        Ok(run)
+    }
+}
+
+/// Drive a robot along a path worked out ahead of time, such as from a map of an earlier run,
+/// w/out looking where it is going.
+pub fn drive<R: Robot>(robot: R, path: &[Key]) -> anyhow::Result<Run> {
+    let robot = CountingRobot::new(robot);
+    let begin = Instant::now();
+
+    for dir in path
+        .windows(2)
+        .filter_map(|pair| pair[0].direction_to(&pair[1]))
+    {
+        robot.go(dir).map_err(|e| {
+            anyhow::Error::from(e).context("Path doesn't match the maze it is driven through.")
+        })?;
+    }
+
+    Ok(Run {
+        path: path.to_vec(),
+        trail: path.to_vec(),
+        stats: Stats {
+            moves: robot.moves(),
+            cells_visited: path.len(),
+            max_depth: path.len(),
+            elapsed: begin.elapsed(),
+            ..Default::default()
+        },
+    })
+}
+
+/// Drive a robot back to the start from wherever it is, along the shortest route on the map it
+/// has built so far.
+pub fn go_home<R: Robot>(robot: &MappingRobot<R>) -> anyhow::Result<Run> {
+    let route = robot
+        .map()
+        .route(robot.position(), |key| *key == Key::default())
+        .ok_or(anyhow!("No known route back to the start!"))?;
+
+    drive(robot, &route)
+}
+
+/// Where the robot's maze says the finish cells are, for solvers that head straight for them.
+async fn finish_keys<R: AsyncRobot>(robot: &R) -> anyhow::Result<Vec<Key>> {
+    Ok(robot
+        .finish_offsets()
+        .await
+        .ok_or(anyhow!(
+            "Solver needs a maze that says where its finish is, like one read w/ --finish-known!"
+        ))?
+        .into_iter()
+        .map(|(east, north)| Key(east, north))
+        .collect())
+}
+
+/// A search that gave up before reaching the finish, w/ the looks & moves it made first.
+#[derive(Debug)]
+pub struct Failed {
+    /// Only the counts kept outside the solver, being peeks, moves & elapsed.
+    pub stats: Stats,
+    pub error: anyhow::Error,
+}
+
+impl Display for Failed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.error, f)
+    }
+}
+
+impl Error for Failed {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
+
+/// What a solver found on its way to the finish.
+#[derive(Debug)]
+pub struct Run {
+    /// Cells from the start to the finish, in order.
+    pub path: Vec<Key>,
+    /// Every cell the robot stood in while searching, in the order it stood in them.
+    pub trail: Vec<Key>,
+    /// How much work the search took.
+    pub stats: Stats,
+}
+
+impl Run {
+    /// Distinct cells the robot stood in while searching.
+    pub fn visited(&self) -> HashSet<Key> {
+        self.trail.iter().copied().collect()
+    }
+
+    /// Number of times the robot stood in each cell while searching.
+    pub fn visit_counts(&self) -> HashMap<Key, usize> {
+        self.trail.iter().fold(HashMap::new(), |mut counts, key| {
+            *counts.entry(*key).or_default() += 1;
+            counts
+        })
+    }
+}
+
+/// Location relative to the start, as (cells east, cells north).
+///
+/// Serializes as a two element array, `[east, north]`.
+#[derive(Debug, Default, Eq, PartialEq, Hash, Copy, Clone, Serialize)]
+pub struct Key(isize, isize);
+
+impl Key {
+    pub fn new(east: isize, north: isize) -> Self {
+        Self(east, north)
+    }
+
+    pub fn compute_in_dir(&self, direction: &Direction) -> Self {
+        match direction {
+            Direction::North => Self(self.0, self.1 + 1),
+            Direction::South => Self(self.0, self.1 - 1),
+            Direction::East => Self(self.0 + 1, self.1),
+            Direction::West => Self(self.0 - 1, self.1),
+        }
+    }
+
+    /// Direction of a single step from this key to the given neighbouring key, if they are
+    /// neighbours.
+    pub fn direction_to(&self, other: &Key) -> Option<Direction> {
+        match (other.0 - self.0, other.1 - self.1) {
+            (0, 1) => Some(Direction::North),
+            (0, -1) => Some(Direction::South),
+            (1, 0) => Some(Direction::East),
+            (-1, 0) => Some(Direction::West),
+            _ => None,
+        }
+    }
+
+    pub fn east(&self) -> isize {
+        self.0
+    }
+
+    pub fn north(&self) -> isize {
+        self.1
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({},{})", self.0, self.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use std::{cell::RefCell, future::poll_fn, rc::Rc, task::Poll};
+
+    use futures::{executor::block_on, future::join_all};
+    use maze_robot::controller::{AsyncMaze, AsyncRobotInternal, Blocking, Cell, Maze, MazeError};
+
+    use super::*;
+    use crate::{
+        edge_maze::{EdgeMaze, EdgeRobot},
+        finish::KnownFinish,
+        text_maze::{TextMaze, TextRobot},
+    };
+
+    /// Search a maze given as text, w/out placing a robot in it first.
+    fn solve(solver: Solver, maze: &str) -> Run {
+        let robot = TextRobot::try_from(maze).expect("maze to create successfully");
+
+        solver.run(robot).expect("solution to be found")
+    }
+
+    /// Place a robot in a maze given as text, w/ the finish known before it sets off.
+    pub fn known_finish_robot(maze: &str) -> TextRobot {
+        TextRobot::new(
+            TextMaze::try_from(maze)
+                .expect("maze to create successfully")
+                .with_known_finish(),
+        )
+    }
+
+    //  . 0 1 2
+    //  0 S   F
+    // -1 +   +
+    // -2     +
+    // -3 +   +
+    // all: (0,0),(1,0),(1,-1),(1,-2),(0,-2),(1,-3),(2,0)
+    // solution: (0,0),(1,0),(2,0)
+    const MULTI_BRANCH_A: &str = "\
+S F
++ +
+  +
++ +";
+    //  . 0 1 2 3 4 5 6
+    //  2 + + + + + + +
+    //  1 +       +   F
+    //  0 S   + + +   +
+    // -1 +           +
+    // -2 + + + + + + +
+    // solution:
+    // (0,0),(1,0),(1,-1),(2,-1),(3,-1),(4,-1),(5,-1),(5,0),(5,1),(6,1)
+    const MULTI_BRANCH_B: &str = "\
++++++++
++   + F
+S +++ +
++     +
++++++++
+";
+
+    #[rstest]
+    #[case("SF",vec![Key(0,0),Key(1,0)])]
+    #[case("S +\n+ F",vec![Key(0,0),Key(1,0),Key(1,-1),Key(2,-1)])]
+    fn can_solve_single_path_mazes(#[case] maze: &str, #[case] exp: Vec<Key>) {
+        let act = solve(Solver::Dfs, maze).path;
+
+        assert_eq!(act, exp)
+    }
+
+    #[rstest]
+    #[case("S F\n+ +",vec![Key(0,0),Key(1,0),Key(2,0)])]
+    #[case(MULTI_BRANCH_A,vec![Key(0,0),Key(1,0),Key(2,0)])]
+    #[case(MULTI_BRANCH_B,vec![Key(0,0),Key(1,0),Key(1,-1),Key(2,-1),Key(3,-1),Key(4,-1),Key(5,-1),Key(5,0),Key(5,1),Key(6,1)])]
+    fn can_solve_deadend_path_mazes(#[case] maze: &str, #[case] exp: Vec<Key>) {
+        let act = solve(Solver::Dfs, maze).path;
+
+        assert_eq!(act, exp)
+    }
+
+    #[rstest]
+    #[case(Solver::Dfs)]
+    #[case(Solver::Bfs)]
+    #[case(Solver::WallFollower)]
+    #[case(Solver::Explore)]
+    #[case(Solver::FloodFill)]
+    #[case(Solver::DStarLite)]
+    fn can_solve_edge_walled_mazes(#[case] solver: Solver) {
+        let maze = "\
++--+--+--+
+|S    |  |
++  +--+  +
+|        |
++--+--+  +
+|F       |
++--+--+--+";
+        let maze = EdgeMaze::try_from(maze).expect("maze to create successfully");
+        let act = solver
+            .run(EdgeRobot::from(maze.with_known_finish()))
+            .expect("solution to be found")
+            .path;
+
+        assert_eq!(act.first(), Some(&Key(0, 0)));
+        assert_eq!(act.last(), Some(&Key(0, -2)));
+    }
+
+    #[rstest]
+    fn can_solve_paths_longer_than_the_call_stack() {
+        let maze = format!("S{}F", " ".repeat(500_000));
+        let act = solve(Solver::Dfs, &maze).path;
+
+        assert_eq!(act.len(), 500_002);
+    }
+
+    #[rstest]
+    fn counts_work_done_before_giving_up() {
+        let robot = TextRobot::try_from("S +\n+  ").expect("maze to create successfully");
+        let act = Solver::Dfs.run(robot).expect_err("search to give up");
+
+        assert_eq!(act.to_string(), "No path to the finish was found!");
+        // in to the far corner & back out again
+        assert_eq!(act.stats.moves, 6);
+        assert!(act.stats.peeks >= act.stats.moves);
+    }
+
+    #[rstest]
+    fn can_drive_known_path() {
+        let robot = TextRobot::try_from("S +\n+ F").expect("maze to create successfully");
+        let path = [Key(0, 0), Key(1, 0), Key(1, -1), Key(2, -1)];
+        let act = drive(robot, &path).expect("path to be driven");
+
+        assert_eq!(act.path, path);
+        assert_eq!((act.stats.moves, act.stats.peeks), (3, 0));
+    }
+
+    #[rstest]
+    fn cannot_drive_through_walls() {
+        let robot = TextRobot::try_from("S+F").expect("maze to create successfully");
+
+        assert!(drive(robot, &[Key(0, 0), Key(1, 0), Key(2, 0)]).is_err());
+    }
+
+    #[rstest]
+    fn can_go_home_by_shortest_known_route() {
+        // dfs heads north first, going all the way round to the finish next to the start
+        let robot = MappingRobot::new(
+            TextRobot::try_from("  \n  \nSF").expect("maze to create successfully"),
+        );
+        let out = Solver::Dfs.run(&robot).expect("solution to be found");
+        let home = go_home(&robot).expect("route home to be found");
+
+        assert_eq!(out.path.len(), 6);
+        assert_eq!(home.path, vec![Key(1, 0), Key(0, 0)]);
+        assert_eq!(robot.position(), Key(0, 0));
+    }
+
+    /// A maze that keeps the robot waiting once before every answer, like one across a network,
+    /// noting which robot asked.
+    #[derive(Debug)]
+    struct SlowMaze {
+        robot: usize,
+        maze: TextMaze,
+        asked: Rc<RefCell<Vec<usize>>>,
+    }
+
+    impl SlowMaze {
+        async fn wait(&self) {
+            let mut waited = false;
+            poll_fn(|cx| {
+                if waited {
+                    return Poll::Ready(());
+                }
+                waited = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            })
+            .await;
+            self.asked.borrow_mut().push(self.robot);
+        }
+    }
+
+    impl AsyncMaze for SlowMaze {
+        async fn look_dir(&self, direction: Direction) -> Cell {
+            self.wait().await;
+            self.maze.look_dir(direction)
+        }
+
+        async fn move_dir(&mut self, direction: Direction) -> Result<(), MazeError> {
+            self.wait().await;
+            self.maze.move_dir(direction)
+        }
+    }
+
+    impl Display for SlowMaze {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            self.maze.fmt(f)
+        }
+    }
+
+    #[rstest]
+    fn can_solve_sync_mazes_async(
+        #[values("S +\n+ F", MULTI_BRANCH_A, MULTI_BRANCH_B)] maze: &str,
+        #[values(
+            Solver::Dfs,
+            Solver::Bfs,
+            Solver::WallFollower,
+            Solver::Explore,
+            Solver::FloodFill,
+            Solver::DStarLite
+        )]
+        solver: Solver,
+    ) {
+        let exp = solver
+            .run(known_finish_robot(maze))
+            .expect("solution to be found");
+        let maze = TextMaze::try_from(maze)
+            .expect("maze to create successfully")
+            .with_known_finish();
+        let act = block_on(solver.run_async(AsyncRobotInternal::new(Blocking(maze))))
+            .expect("solution to be found");
+
+        assert_eq!((&act.path, &act.trail), (&exp.path, &exp.trail));
+        assert_eq!(
+            Stats {
+                elapsed: exp.stats.elapsed,
+                ..act.stats
+            },
+            exp.stats
+        );
+    }
+
+    #[rstest]
+    fn can_run_many_robots_on_one_executor() {
+        let asked = Rc::new(RefCell::new(vec![]));
+        let robots = (0..3).map(|robot| {
+            AsyncRobotInternal::new(SlowMaze {
+                robot,
+                maze: TextMaze::try_from("S +\n+ F").expect("maze to create successfully"),
+                asked: asked.clone(),
+            })
+        });
+        let runs = block_on(join_all(robots.map(|robot| Solver::Dfs.run_async(robot))));
+
+        for run in runs {
+            assert_eq!(run.expect("solution to be found").path.len(), 4);
+        }
+        // every robot gets its first answer before any gets a second
+        assert_eq!(asked.borrow()[..3], [0, 1, 2]);
+    }
+
+    #[rstest]
+    fn cannot_solve_async_w_out_finish() {
+        let maze = TextMaze::try_from("S +\n+  ").expect("maze to create successfully");
+
+        assert!(block_on(Solver::Dfs.run_async(AsyncRobotInternal::new(Blocking(maze)))).is_err());
+    }
+
+    #[rstest]
+    fn async_robots_hear_where_finish_is() {
+        let maze = TextMaze::try_from("S F")
+            .expect("maze to create successfully")
+            .with_known_finish();
+        let robot = CountingRobot::new(AsyncRobotInternal::new(Blocking(maze)));
+
+        assert_eq!(block_on(robot.finish_offsets()), Some(vec![(2, 0)]));
+    }
+
+    #[rstest]
+    #[case(Solver::Dfs)]
+    #[case(Solver::WallFollower)]
+    fn counts_work_done(#[case] solver: Solver) {
+        // both solvers try the dead end to the north first & have to back out of it
+        let act = solve(solver, " ++\nS F").stats;
+
+        assert_eq!(
+            (act.moves, act.backtracks, act.cells_visited, act.max_depth),
+            (4, 1, 4, 3)
+        );
+        assert!(act.peeks >= act.moves);
+    }
+}
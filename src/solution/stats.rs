@@ -0,0 +1,118 @@
+use std::{cell::Cell as Counter, fmt::Display, time::Duration};
+
+use maze_robot::controller::{AsyncRobot, Cell, Direction, MazeError, Robot, RobotInternal};
+
+/// How much work a solver did to find the finish.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Stats {
+    /// Times the robot looked in a direction.
+    pub peeks: usize,
+    /// Times the robot tried to move, including moves into walls.
+    pub moves: usize,
+    /// Moves back into a cell already on the way from the start to the robot.
+    pub backtracks: usize,
+    /// Distinct cells the robot stood in.
+    pub cells_visited: usize,
+    /// Most cells on the route the solver was following at once, such as the DFS stack.
+    pub max_depth: usize,
+    /// Wall time from the first look to finding the finish.
+    pub elapsed: Duration,
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "peeks:         {}", self.peeks)?;
+        writeln!(f, "moves:         {}", self.moves)?;
+        writeln!(f, "backtracks:    {}", self.backtracks)?;
+        writeln!(f, "cells visited: {}", self.cells_visited)?;
+        writeln!(f, "max depth:     {}", self.max_depth)?;
+        write!(f, "elapsed:       {:?}", self.elapsed)
+    }
+}
+
+/// A robot that counts every look & move made through it.
+pub struct CountingRobot<R> {
+    robot: R,
+    peeks: Counter<usize>,
+    moves: Counter<usize>,
+}
+
+impl<R> CountingRobot<R> {
+    pub fn new(robot: R) -> Self {
+        Self {
+            robot,
+            peeks: Counter::new(0),
+            moves: Counter::new(0),
+        }
+    }
+
+    pub fn peeks(&self) -> usize {
+        self.peeks.get()
+    }
+
+    pub fn moves(&self) -> usize {
+        self.moves.get()
+    }
+}
+
+impl<R: Robot> Robot for CountingRobot<R> {
+    fn get_internal(&self) -> &RobotInternal {
+        self.robot.get_internal()
+    }
+
+    fn peek(&self, direction: Direction) -> Cell {
+        self.peeks.set(self.peeks.get() + 1);
+        self.robot.peek(direction)
+    }
+
+    fn peek_all(&self) -> [(Cell, Direction); 4] {
+        self.peeks.set(self.peeks.get() + 4);
+        self.robot.peek_all()
+    }
+
+    fn go(&self, direction: Direction) -> Result<(), MazeError> {
+        self.moves.set(self.moves.get() + 1);
+        self.robot.go(direction)
+    }
+}
+
+impl<R: AsyncRobot> AsyncRobot for CountingRobot<R> {
+    async fn peek(&self, direction: Direction) -> Cell {
+        self.peeks.set(self.peeks.get() + 1);
+        self.robot.peek(direction).await
+    }
+
+    async fn peek_all(&self) -> [(Cell, Direction); 4] {
+        self.peeks.set(self.peeks.get() + 4);
+        self.robot.peek_all().await
+    }
+
+    async fn go(&self, direction: Direction) -> Result<(), MazeError> {
+        self.moves.set(self.moves.get() + 1);
+        self.robot.go(direction).await
+    }
+
+    async fn finish_offsets(&self) -> Option<Vec<(isize, isize)>> {
+        self.robot.finish_offsets().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::text_maze::TextRobot;
+
+    #[rstest]
+    fn test_counts_looks_and_moves() {
+        let robot = CountingRobot::new(TextRobot::try_from("S F").expect("maze to create"));
+
+        robot.peek(Direction::East);
+        robot.peek_all();
+        robot.go(Direction::East).expect("move to succeed");
+        assert!(robot.go(Direction::North).is_err());
+
+        assert_eq!((robot.peeks(), robot.moves()), (5, 2));
+    }
+}
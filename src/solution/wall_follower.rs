@@ -0,0 +1,102 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::anyhow;
+
+use maze_robot::controller::{AsyncRobot, Cell, Direction, Robot};
+
+use crate::solution::{
+    Key, Run, Stats,
+    immediate::{Immediate, run_now},
+};
+
+/// Follow the wall on the robot's left until the finish is found.
+///
+/// Loops the robot walks while following the wall are cut out of the returned path, so the
+/// result only visits each cell once. If the robot ever finds itself back in the same cell facing
+/// the same way, it is circling something w/out a finish on it & gives up.
+pub fn wall_follower_path<R: Robot>(robot: &R) -> anyhow::Result<Run> {
+    run_now(wall_follower_path_async(&Immediate(robot)))
+}
+
+/// Follow the wall like `wall_follower_path`, waiting on the robot's maze rather than blocking on
+/// it.
+pub async fn wall_follower_path_async<R: AsyncRobot>(robot: &R) -> anyhow::Result<Run> {
+    let mut key = Key(0, 0);
+    let mut heading = Direction::North;
+    let mut path = vec![key];
+    let mut trail = vec![key];
+    let mut path_idx = HashMap::from([(key, 0)]);
+    let mut seen = HashSet::new();
+    let mut stats = Stats::default();
+
+    loop {
+        if !seen.insert((key, heading)) {
+            return Err(anyhow!("No path to the finish was found!"));
+        }
+
+        // prefer turning left, then straight, then right, then turning around
+        let mut open = None;
+        for dir in [heading.left(), heading, heading.right(), heading.reverse()] {
+            let cell = robot.peek(dir).await;
+            if cell != Cell::Wall {
+                open = Some((dir, cell));
+                break;
+            }
+        }
+        let (dir, cell) = open.ok_or(anyhow!("No path to the finish was found!"))?;
+
+        robot.go(dir).await.map_err(|e| {
+            anyhow::Error::from(e).context("Error encountered while following wall.")
+        })?;
+        heading = dir;
+        key = key.compute_in_dir(&dir);
+        trail.push(key);
+
+        // returning to a cell already on the path means the walk since then was a detour
+        match path_idx.get(&key) {
+            Some(&idx) => {
+                stats.backtracks += 1;
+                path.drain(idx + 1..).for_each(|k| {
+                    path_idx.remove(&k);
+                });
+            }
+            None => {
+                path_idx.insert(key, path.len());
+                path.push(key);
+                stats.max_depth = stats.max_depth.max(path.len());
+            }
+        }
+
+        if let Cell::Finish = cell {
+            return Ok(Run { path, trail, stats });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::text_maze::TextRobot;
+
+    #[rstest]
+    #[case("S +\n+ F", vec![Key(0,0),Key(1,0),Key(1,-1),Key(2,-1)])]
+    #[case("S F\n+ +\n  +\n+ +", vec![Key(0,0),Key(1,0),Key(2,0)])]
+    fn can_follow_wall_to_finish(#[case] maze: &str, #[case] exp: Vec<Key>) {
+        let robot = TextRobot::try_from(maze).expect("Robot creates successfully");
+        let act = wall_follower_path(&robot)
+            .expect("solution to be found")
+            .path;
+
+        assert_eq!(act, exp)
+    }
+
+    #[rstest]
+    fn gives_up_when_circling() {
+        let robot = TextRobot::try_from("+++++\n+S  +\n+ + +\n+   +\n+++++\n+F+++")
+            .expect("Robot creates successfully");
+
+        assert!(wall_follower_path(&robot).is_err())
+    }
+}
@@ -0,0 +1,160 @@
+use std::fmt::Write;
+
+use maze_robot::controller::Cell;
+
+use crate::{render::grid_posn, solution::Run, text_maze::TextMaze};
+
+/// Side length of one maze cell, in SVG user units.
+const CELL: usize = 10;
+
+/// Optional layers drawn on top of the maze's walls.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Layers {
+    /// Line through the centre of each cell on the solution path
+    pub path: bool,
+    /// Tint on every cell the robot stood in
+    pub visited: bool,
+    /// Shading from yellow to red by how many times the robot stood in each cell
+    pub heatmap: bool,
+}
+
+/// Draw the maze as an SVG document, w/ any requested layers taken from the given run.
+///
+/// Walls are black squares, the start green & the finish red. Layers are stacked in the order
+/// heatmap, visited, path, so the path is always visible.
+pub fn to_svg(maze: &TextMaze, run: Option<&Run>, layers: Layers) -> String {
+    let (width, height) = (maze.width() * CELL, maze.height() * CELL);
+    let mut out = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+    let _ = write!(
+        out,
+        "\n<rect width=\"{width}\" height=\"{height}\" fill=\"white\"/>"
+    );
+
+    for row in 0..maze.height() {
+        for col in 0..maze.width() {
+            let fill = match maze.cell_at((col, row)) {
+                _ if (col, row) == maze.start() => "green",
+                Cell::Wall => "black",
+                Cell::Finish => "red",
+                Cell::Open => continue,
+            };
+            rect(&mut out, (col, row), fill, 1.0);
+        }
+    }
+
+    if let Some(run) = run {
+        if layers.heatmap {
+            let counts = run.visit_counts();
+            let max = counts.values().copied().max().unwrap_or(1);
+
+            for (key, count) in counts {
+                if let Some(posn) = grid_posn(maze, &key) {
+                    // more visits fade from yellow to red
+                    let green = 255 - 255 * count / max;
+                    rect(&mut out, posn, &format!("rgb(255,{green},0)"), 0.6);
+                }
+            }
+        }
+
+        if layers.visited {
+            for key in run.visited() {
+                if let Some(posn) = grid_posn(maze, &key) {
+                    rect(&mut out, posn, "steelblue", 0.3);
+                }
+            }
+        }
+
+        if layers.path {
+            let points: Vec<String> = run
+                .path
+                .iter()
+                .filter_map(|key| grid_posn(maze, key))
+                .map(|(col, row)| format!("{},{}", centre(col), centre(row)))
+                .collect();
+            let _ = write!(
+                out,
+                "\n<polyline points=\"{}\" fill=\"none\" stroke=\"blue\" stroke-width=\"{}\" stroke-linejoin=\"round\"/>",
+                points.join(" "),
+                CELL / 3
+            );
+        }
+    }
+
+    out.push_str("\n</svg>\n");
+    out
+}
+
+fn rect(out: &mut String, (col, row): (usize, usize), fill: &str, opacity: f32) {
+    let _ = write!(
+        out,
+        "\n<rect x=\"{}\" y=\"{}\" width=\"{CELL}\" height=\"{CELL}\" fill=\"{fill}\" fill-opacity=\"{opacity}\"/>",
+        col * CELL,
+        row * CELL
+    );
+}
+
+fn centre(idx: usize) -> usize {
+    idx * CELL + CELL / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{solution::Solver, text_maze::TextRobot};
+
+    const MAZE: &str = "\
++++++++
++   + F
+S +++ +
++     +
++++++++";
+
+    fn svg(layers: Layers) -> String {
+        let maze = TextMaze::try_from(MAZE).expect("maze to create successfully");
+        let run = Solver::Dfs
+            .run(TextRobot::try_from(MAZE).expect("maze to create successfully"))
+            .expect("solution to be found");
+
+        to_svg(&maze, Some(&run), layers)
+    }
+
+    #[rstest]
+    fn test_draws_walls_start_and_finish() {
+        let act = svg(Layers::default());
+
+        assert!(act.starts_with("<svg "));
+        assert!(act.ends_with("</svg>\n"));
+        assert_eq!(act.matches("fill=\"black\"").count(), 22);
+        assert_eq!(act.matches("fill=\"green\"").count(), 1);
+        assert_eq!(act.matches("fill=\"red\"").count(), 1);
+        assert!(!act.contains("<polyline"));
+    }
+
+    #[rstest]
+    fn test_path_layer() {
+        let act = svg(Layers {
+            path: true,
+            ..Default::default()
+        });
+
+        assert!(act.contains("<polyline points=\"5,25 15,25 15,35 25,35 "));
+    }
+
+    #[rstest]
+    fn test_visited_and_heatmap_layers() {
+        let act = svg(Layers {
+            visited: true,
+            heatmap: true,
+            ..Default::default()
+        });
+
+        // the 10 path cells & the 3 cell dead end north of the path
+        assert_eq!(act.matches("fill=\"steelblue\"").count(), 13);
+        // cells the robot had to back up through are stood in twice, the most of any cell
+        assert_eq!(act.matches("fill=\"rgb(255,0,0)\"").count(), 3);
+    }
+}
@@ -0,0 +1,89 @@
+use maze_robot::controller::Cell;
+
+/// Cells packed 4 to a byte, so huge mazes take a quarter of a byte per cell instead of the 4
+/// bytes of a `char`.
+#[derive(Debug, Default)]
+pub struct PackedGrid {
+    bits: Vec<u8>,
+    len: usize,
+}
+
+const CELLS_PER_BYTE: usize = 4;
+
+impl PackedGrid {
+    /// Empty grid w/ room for the given number of cells.
+    pub fn with_capacity(cells: usize) -> Self {
+        Self {
+            bits: Vec::with_capacity(cells.div_ceil(CELLS_PER_BYTE)),
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn push(&mut self, cell: Cell) {
+        if self.len.is_multiple_of(CELLS_PER_BYTE) {
+            self.bits.push(0);
+        }
+        let (byte, shift) = Self::locate(self.len);
+        self.bits[byte] |= encode(cell) << shift;
+        self.len += 1;
+    }
+
+    pub fn get(&self, idx: usize) -> Option<Cell> {
+        (idx < self.len).then(|| {
+            let (byte, shift) = Self::locate(idx);
+            decode(self.bits[byte] >> shift & 0b11)
+        })
+    }
+
+    fn locate(idx: usize) -> (usize, u32) {
+        (idx / CELLS_PER_BYTE, (idx % CELLS_PER_BYTE) as u32 * 2)
+    }
+}
+
+fn encode(cell: Cell) -> u8 {
+    match cell {
+        Cell::Wall => 0,
+        Cell::Open => 1,
+        Cell::Finish => 2,
+    }
+}
+
+fn decode(bits: u8) -> Cell {
+    match bits {
+        1 => Cell::Open,
+        2 => Cell::Finish,
+        _ => Cell::Wall,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    fn test_round_trip() {
+        let cells = [
+            Cell::Wall,
+            Cell::Open,
+            Cell::Finish,
+            Cell::Open,
+            Cell::Finish,
+        ];
+        let mut grid = PackedGrid::with_capacity(cells.len());
+        cells.iter().for_each(|&cell| grid.push(cell));
+
+        assert_eq!(grid.len(), 5);
+        assert_eq!(grid.bits.len(), 2);
+        assert_eq!(
+            (0..5).map(|idx| grid.get(idx)).collect::<Option<Vec<_>>>(),
+            Some(cells.to_vec())
+        );
+        assert_eq!(grid.get(5), None);
+    }
+}
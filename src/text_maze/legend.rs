@@ -0,0 +1,178 @@
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use maze_robot::controller::{Cell, MazeError};
+
+/// What a character in a text maze stands for.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    Wall,
+    Open,
+    Start,
+    Finish,
+}
+
+/// The start is an open cell to anything but the maze that places the robot there.
+impl From<Role> for Cell {
+    fn from(value: Role) -> Self {
+        match value {
+            Role::Wall => Cell::Wall,
+            Role::Open | Role::Start => Cell::Open,
+            Role::Finish => Cell::Finish,
+        }
+    }
+}
+
+/// Which characters stand for which roles in a text maze, w/ any character left out being open,
+/// except Unicode box-drawing characters, which are walls so mazes drawn w/ them can be read.
+///
+/// Written as space separated `char=role` pairs, like `#=wall .=open @=start E=finish`, where
+/// `space` can stand in for a ' ' character.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Legend(BTreeMap<char, Role>);
+
+/// The Unicode block of box-drawing characters, like '┌', '─' & '┼'.
+const BOX_DRAWING: std::ops::RangeInclusive<char> = '\u{2500}'..='\u{257F}';
+
+/// Line a maze file can start w/ to give its own legend.
+const HEADER: &str = "legend:";
+
+/// The characters written by every maze in this crate:
+/// - 'S' is starting location
+/// - 'F' is ending location
+/// - '+' is a wall
+/// - all others are considered open
+impl Default for Legend {
+    fn default() -> Self {
+        Self(BTreeMap::from([
+            ('S', Role::Start),
+            ('F', Role::Finish),
+            ('+', Role::Wall),
+        ]))
+    }
+}
+
+impl Legend {
+    pub fn role(&self, chr: char) -> Role {
+        match self.0.get(&chr) {
+            Some(&role) => role,
+            None if BOX_DRAWING.contains(&chr) => Role::Wall,
+            None => Role::Open,
+        }
+    }
+
+    /// Split a `legend: ...` first line off the maze text, if it has one.
+    pub fn from_header(text: &str) -> Result<Option<(Self, &str)>, MazeError> {
+        let Some(rest) = text.strip_prefix(HEADER) else {
+            return Ok(None);
+        };
+        let (line, body) = rest.split_once('\n').unwrap_or((rest, ""));
+
+        Ok(Some((line.trim_end_matches('\r').parse()?, body)))
+    }
+}
+
+impl FromStr for Legend {
+    type Err = MazeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let roles = s
+            .split_whitespace()
+            .map(|pair| {
+                let invalid = || {
+                    MazeError::CreationError(format!(
+                        "legend entry '{pair}' must be a character, '=' & one of wall, open, start or finish"
+                    ))
+                };
+                let (key, role) = pair.split_once('=').ok_or_else(invalid)?;
+                let mut chars = key.chars();
+                let chr = match (key, chars.next(), chars.next()) {
+                    ("space", _, _) => ' ',
+                    (_, Some(chr), None) => chr,
+                    _ => return Err(invalid()),
+                };
+                let role = match role {
+                    "wall" => Role::Wall,
+                    "open" => Role::Open,
+                    "start" => Role::Start,
+                    "finish" => Role::Finish,
+                    _ => return Err(invalid()),
+                };
+
+                Ok((chr, role))
+            })
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
+
+        if roles.is_empty() {
+            Err(MazeError::CreationError(String::from("legend is empty")))
+        } else {
+            Ok(Self(roles))
+        }
+    }
+}
+
+impl Display for Legend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pairs: Vec<String> = self
+            .0
+            .iter()
+            .map(|(chr, role)| {
+                let key = match chr {
+                    ' ' => String::from("space"),
+                    chr => chr.to_string(),
+                };
+                format!("{key}={}", format!("{role:?}").to_lowercase())
+            })
+            .collect();
+
+        write!(f, "{}", pairs.join(" "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::*;
+
+    #[rstest]
+    fn test_parse_round_trip() {
+        let legend: Legend = "#=wall space=open @=start E=finish"
+            .parse()
+            .expect("legend to parse");
+
+        assert_eq!(legend.role('#'), Role::Wall);
+        assert_eq!(legend.role('@'), Role::Start);
+        assert_eq!(legend.role('+'), Role::Open);
+        assert_eq!(legend.role('┼'), Role::Wall);
+        assert_eq!(legend.to_string().parse::<Legend>().ok(), Some(legend));
+    }
+
+    #[rstest]
+    fn test_from_header() {
+        let (legend, body) = Legend::from_header("legend: #=wall\r\n#@#\n")
+            .expect("header to parse")
+            .expect("header to be found");
+
+        assert_eq!(legend.role('#'), Role::Wall);
+        assert_eq!(body, "#@#\n");
+        assert_eq!(
+            Legend::from_header("S F").expect("no header to be ok"),
+            None
+        );
+    }
+
+    #[rstest]
+    #[case::empty("")]
+    #[case::no_role("#")]
+    #[case::long_key("##=wall")]
+    #[case::unknown_role("#=lava")]
+    fn test_invalid(#[case] legend: &str) {
+        match legend.parse::<Legend>() {
+            Err(MazeError::CreationError(_)) => (),
+            other => panic!("expected CreationError, got {other:?}"),
+        }
+    }
+}
@@ -1,62 +1,107 @@
-use std::fmt::Display;
+use std::{
+    fmt::{Display, Write},
+    ops::Range,
+};
 
-use maze_robot::controller::{Cell, Direction, Maze, MazeError};
+use clap::ValueEnum;
 
-use crate::text_maze::TextCell;
+use maze_robot::controller::{Cell, Direction, Maze, MazeError, SourceLocation};
 
-/// A maze encoded by a string, where:
+use crate::{
+    finish::{Finish, KnownFinish},
+    text_maze::{Legend, Role, grid::PackedGrid},
+};
+
+/// How many cells around the robot a `MoveError` shows.
+const ERROR_RADIUS: usize = 3;
+
+/// A maze encoded by a string, where by default:
 /// - 'S' is starting location
 /// - 'F' is ending location
 /// - '+' & out of bounds are considered walls
 /// - all others are considered open
 ///
+/// Other characters can be used by giving a `Legend`.
+///
+/// Cells are kept packed as their `Cell` rather than their character, so the maze is drawn back
+/// out w/ ' ' for every open cell.
+///
 /// Tracks robot location as private state used by the two `Maze` trait methods.
 #[derive(Debug)]
 pub struct TextMaze {
-    chars: Vec<char>,
+    cells: PackedGrid,
     loc: usize,
+    start: usize,
+    finish: Finish,
     width: usize,
+    height: usize,
 }
 
 impl TextMaze {
-    fn get_posn_in_dir(&self, direction: Direction) -> Option<usize> {
+    /// Number of columns in each line of the maze.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of lines in the maze.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Grid position (column, row) of the 'S' cell.
+    pub fn start(&self) -> (usize, usize) {
+        self.idx_to_posn(self.start)
+    }
+
+    /// Grid position (column, row) the robot currently occupies.
+    pub fn position(&self) -> (usize, usize) {
+        self.idx_to_posn(self.loc)
+    }
+
+    /// Cell found at the given grid position, where anything out of bounds is a wall.
+    pub fn cell_at(&self, (col, row): (usize, usize)) -> Cell {
+        if col >= self.width {
+            return Cell::Wall;
+        }
+
+        self.cells.get(row * self.width + col).unwrap_or(Cell::Wall)
+    }
+
+    /// Grid position one step in the given direction, if it is still inside the grid.
+    pub fn step(&self, (col, row): (usize, usize), direction: Direction) -> Option<(usize, usize)> {
         match direction {
-            Direction::North => {
-                // no up if in top row
-                if self.loc <= self.width {
-                    None
-                } else {
-                    Some(self.loc - self.width - 1)
-                }
-            }
-            Direction::South => {
-                // go down one row by adding width & accounting for newline char
-                let pos = self.loc + self.width + 1;
-                // no down if past end of chars vec
-                if pos >= self.chars.len() {
-                    None
-                } else {
-                    Some(pos)
-                }
-            }
-            Direction::East => {
-                // go right one col by incrementing pos
-                let pos = self.loc + 1;
-                // no right if past end of chars vec
-                if pos >= self.chars.len() {
-                    None
-                } else {
-                    Some(pos)
-                }
-            }
-            Direction::West => {
-                // no left if loc already at start
-                if self.loc == 0 {
-                    None
-                } else {
-                    // go left one col by decrementing pos
-                    Some(self.loc - 1)
-                }
+            Direction::North => row.checked_sub(1).map(|r| (col, r)),
+            Direction::South => (row + 1 < self.height).then_some((col, row + 1)),
+            Direction::East => (col + 1 < self.width).then_some((col + 1, row)),
+            Direction::West => col.checked_sub(1).map(|c| (c, row)),
+        }
+    }
+
+    /// The part of the maze within `radius` cells of the given position, drawn only when
+    /// displayed.
+    pub fn window(&self, (col, row): (usize, usize), radius: usize) -> Window<'_> {
+        Window {
+            maze: self,
+            cols: col.saturating_sub(radius)..(col + radius + 1).min(self.width),
+            rows: row.saturating_sub(radius)..(row + radius + 1).min(self.height),
+        }
+    }
+
+    fn idx_to_posn(&self, idx: usize) -> (usize, usize) {
+        (idx % self.width, idx / self.width)
+    }
+
+    /// Character drawn for the given position, w/ the robot as 'X'.
+    fn glyph(&self, posn: (usize, usize)) -> char {
+        if posn == self.position() {
+            'X'
+        } else if posn == self.start() {
+            'S'
+        } else {
+            match self.cell_at(posn) {
+                Cell::Wall => '+',
+                Cell::Open => ' ',
+                Cell::Finish => 'F',
             }
         }
     }
@@ -64,87 +109,220 @@ impl TextMaze {
 
 impl Maze for TextMaze {
     fn look_dir(&self, direction: Direction) -> Cell {
-        self.get_posn_in_dir(direction)
-            .and_then(|pos| self.chars.get(pos))
-            .map(|chr| TextCell::from(chr).into())
+        self.step(self.position(), direction)
+            .map(|posn| self.cell_at(posn))
             .unwrap_or(Cell::Wall)
     }
 
     fn move_dir(&mut self, direction: Direction) -> Result<(), MazeError> {
-        self.loc = self
-            .get_posn_in_dir(direction)
-            .and_then(|pos| {
-                let cell = self.chars.get(pos).map(|chr| TextCell::from(chr).into())?;
-                match cell {
-                    Cell::Wall => None,
-                    _ => Some(pos),
-                }
-            })
-            .ok_or(MazeError::MoveError(direction, self.to_string()))?;
+        let position = self.position();
+        match self.step(position, direction) {
+            Some((col, row)) if self.cell_at((col, row)) != Cell::Wall => {
+                self.loc = row * self.width + col;
+                Ok(())
+            }
+            _ => Err(MazeError::MoveError {
+                direction,
+                position,
+                blocked_by: Cell::Wall,
+                surroundings: self.window(position, ERROR_RADIUS).to_string(),
+            }),
+        }
+    }
 
-        Ok(())
+    fn finish_offsets(&self) -> Option<Vec<(isize, isize)>> {
+        self.finish.offsets_from(self.position())
     }
 }
 
-impl TryFrom<&str> for TextMaze {
-    type Error = MazeError;
+impl KnownFinish for TextMaze {
+    fn finish_mut(&mut self) -> &mut Finish {
+        &mut self.finish
+    }
+}
 
-    fn try_from(value: &str) -> Result<Self, Self::Error> {
-        let (chars, maybe_loc, maybe_width) =
-            value
-                .chars()
-                .enumerate()
-                .try_fold((vec![], None, None), |mut acc, (idx, chr)| {
-                    match chr {
-                        'S' => acc.1 = Some(idx),
-                        '\n' => match acc.2 {
-                            Some(width) => {
-                                if ((idx + 1) % (width + 1)) != 0 {
-                                    return Err(MazeError::CreationError(String::from(
-                                        "TextMaze must have all lines with equal lengths.",
-                                    )));
-                                }
-                            }
-                            None => acc.2 = Some(idx),
-                        },
-                        _ => (),
+/// How forgiving `TextMaze::parse` is of badly laid out text.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum ParseMode {
+    /// Reject the maze, reporting every ragged line & extra start found
+    #[default]
+    Strict,
+    /// Tidy up text from other editors & systems before reading it: lone '\r' line endings are
+    /// normalized, tabs expanded to the next multiple of 8 columns, trailing blank lines dropped
+    /// & short lines padded w/ walls to the width of the longest
+    Lenient,
+}
+
+/// Columns between tab stops when expanding tabs.
+const TAB_WIDTH: usize = 8;
+
+impl TextMaze {
+    /// Build a maze from text, tidying it up first if asked to.
+    ///
+    /// Characters are read through the given legend, unless the text starts w/ a `legend:` line
+    /// giving its own.
+    pub fn parse(value: &str, mode: ParseMode, legend: &Legend) -> Result<Self, MazeError> {
+        let (legend, body, first_line) = match Legend::from_header(value)? {
+            Some((own, body)) => (own, body, 2),
+            None => (legend.clone(), value, 1),
+        };
+
+        match mode {
+            ParseMode::Strict => Self::read_grid(body, &legend, first_line, mode),
+            ParseMode::Lenient => Self::read_grid(&tidy(body), &legend, first_line, mode),
+        }
+    }
+
+    /// Read the cells line by line, w/ `first_line` being the line number of the first in the
+    /// file, for pointing errors at the right place.
+    fn read_grid(
+        value: &str,
+        legend: &Legend,
+        first_line: usize,
+        mode: ParseMode,
+    ) -> Result<Self, MazeError> {
+        if value.lines().all(str::is_empty) {
+            return Err(MazeError::EmptyInput);
+        }
+
+        let width = match mode {
+            ParseMode::Strict => value.lines().next().map(|line| line.chars().count()),
+            ParseMode::Lenient => value.lines().map(|line| line.chars().count()).max(),
+        }
+        .unwrap_or_default();
+        let mut cells = PackedGrid::with_capacity(value.len());
+        let mut start: Option<(usize, SourceLocation)> = None;
+        let mut finish = vec![];
+        let mut errors = vec![];
+        let mut height = 0;
+
+        for (line_idx, line) in value.lines().enumerate() {
+            let before = cells.len();
+            for (col, chr) in line.chars().enumerate().take(width) {
+                let role = legend.role(chr);
+                if role == Role::Start {
+                    let at = SourceLocation {
+                        line: line_idx + first_line,
+                        column: col + 1,
                     };
+                    match start {
+                        Some((_, first)) => {
+                            errors.push(MazeError::MultipleStarts { first, second: at })
+                        }
+                        None => start = Some((cells.len(), at)),
+                    }
+                }
+                if role == Role::Finish {
+                    finish.push((col, height));
+                }
+                cells.push(role.into());
+            }
+
+            let len = line.chars().count();
+            if len != width && matches!(mode, ParseMode::Strict) {
+                errors.push(MazeError::RaggedLine {
+                    at: SourceLocation {
+                        line: line_idx + first_line,
+                        column: len.min(width) + 1,
+                    },
+                    expected_width: width,
+                });
+            }
+            // pad short lines w/ walls, so lenient mazes stay rectangular & strict ones still
+            // check later lines in the right place
+            (cells.len() - before..width).for_each(|_| cells.push(Cell::Wall));
+            height += 1;
+        }
+
+        if start.is_none() {
+            errors.push(MazeError::MissingStart);
+        }
+        match (errors.len(), start) {
+            (0, Some((loc, _))) => Ok(TextMaze {
+                cells,
+                loc,
+                start: loc,
+                finish: Finish::new(finish),
+                width,
+                height,
+            }),
+            (1, _) => Err(errors.remove(0)),
+            _ => Err(MazeError::Multiple(errors)),
+        }
+    }
+}
+
+/// Normalize line endings, expand tabs & drop trailing blank lines.
+fn tidy(value: &str) -> String {
+    let mut lines: Vec<String> = value
+        .replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .lines()
+        .map(|line| {
+            let mut out = String::with_capacity(line.len());
+            let mut col = 0;
+            for chr in line.chars() {
+                let next = match chr {
+                    '\t' => (col / TAB_WIDTH + 1) * TAB_WIDTH,
+                    _ => col + 1,
+                };
+                match chr {
+                    '\t' => out.extend(std::iter::repeat_n(' ', next - col)),
+                    _ => out.push(chr),
+                }
+                col = next;
+            }
+            out
+        })
+        .collect();
+    // a line of spaces is a row of open cells, so only lines w/ nothing on them are blank
+    while lines.last().is_some_and(|line| line.is_empty()) {
+        lines.pop();
+    }
 
-                    acc.0.push(chr);
-                    Ok(acc)
-                })?;
+    lines.join("\n")
+}
 
-        let loc = maybe_loc.ok_or(MazeError::CreationError(String::from(
-            "TextMaze must specify start location w/ 'S'",
-        )))?;
-        let width = match maybe_width {
-            Some(w) => Ok(w),
-            None if !chars.iter().all(|c| c == &'\n') => Ok(chars.len()),
-            _ => Err(MazeError::CreationError(String::from(
-                "TextMaze cannot have empty lines",
-            ))),
-        }?;
+impl TryFrom<&str> for TextMaze {
+    type Error = MazeError;
 
-        Ok(TextMaze { chars, loc, width })
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::parse(value, ParseMode::Strict, &Legend::default())
     }
 }
 
 impl Display for TextMaze {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let marked: Vec<String> = self
-            .chars
-            .iter()
-            .enumerate()
-            .map(|(idx, chr)| {
-                if idx == self.loc {
-                    String::from("X")
-                } else {
-                    chr.to_string()
-                }
-            })
-            .collect();
+        Window {
+            maze: self,
+            cols: 0..self.width,
+            rows: 0..self.height,
+        }
+        .fmt(f)
+    }
+}
 
-        write!(f, "{}", marked.join(""))
+/// A rectangle of a maze that is drawn a character at a time, so even a view of a whole huge maze
+/// never builds more than the output needs.
+#[derive(Debug)]
+pub struct Window<'a> {
+    maze: &'a TextMaze,
+    cols: Range<usize>,
+    rows: Range<usize>,
+}
+
+impl Display for Window<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows.clone() {
+            if row != self.rows.start {
+                f.write_char('\n')?;
+            }
+            for col in self.cols.clone() {
+                f.write_char(self.maze.glyph((col, row)))?;
+            }
+        }
+
+        Ok(())
     }
 }
 
@@ -191,11 +369,146 @@ S+"#;
         match maze.move_dir(direction) {
             Ok(_) => panic!(
                 "should have returned error when trying to move {direction:?} in maze:\n{state}\ninstead, got new state:\n{}",
-                maze.to_string()
+                maze
             ),
 
-            Err(MazeError::MoveError(_, _)) => (),
+            Err(MazeError::MoveError { .. }) => (),
             Err(e) => panic!("expected UpdateError, got {e:?}"),
         }
     }
+
+    #[rstest]
+    fn test_window_clips_to_grid() {
+        let maze = TextMaze::try_from("+++++\n+S  +\n+ + F\n+++++").expect("maze to create");
+
+        assert_eq!(maze.window((1, 1), 1).to_string(), "+++\n+X \n+ +");
+        assert_eq!(maze.window((4, 3), 1).to_string(), " F\n++");
+    }
+
+    #[rstest]
+    fn test_move_error_shows_only_nearby_cells() {
+        let wide = format!("S{}F", " ".repeat(100));
+        let mut maze = TextMaze::try_from(wide.as_str()).expect("maze to create");
+
+        match maze.move_dir(Direction::West) {
+            Err(MazeError::MoveError {
+                position,
+                blocked_by,
+                surroundings,
+                ..
+            }) => {
+                assert_eq!(position, (0, 0));
+                assert_eq!(blocked_by, Cell::Wall);
+                assert_eq!(surroundings, "X   ");
+            }
+            other => panic!("expected MoveError, got {other:?}"),
+        }
+    }
+
+    #[rstest]
+    #[case::empty("", MazeError::EmptyInput)]
+    #[case::blank_lines("\n\n", MazeError::EmptyInput)]
+    #[case::no_start("  \n  ", MazeError::MissingStart)]
+    #[case::short_line(
+        "S  \n+ +\n+",
+        MazeError::RaggedLine { at: SourceLocation { line: 3, column: 2 }, expected_width: 3 }
+    )]
+    #[case::long_line(
+        "S \n+++",
+        MazeError::RaggedLine { at: SourceLocation { line: 2, column: 3 }, expected_width: 2 }
+    )]
+    #[case::two_starts(
+        "S \n S",
+        MazeError::MultipleStarts {
+            first: SourceLocation { line: 1, column: 1 },
+            second: SourceLocation { line: 2, column: 2 },
+        }
+    )]
+    fn test_invalid(#[case] maze: &str, #[case] exp: MazeError) {
+        match TextMaze::try_from(maze) {
+            Err(act) => assert_eq!(act, exp),
+            Ok(_) => panic!("expected {exp:?}"),
+        }
+    }
+
+    #[rstest]
+    #[case::crlf("+++\r\n+S \r\n+++\r\n", "+++\n+X \n+++")]
+    #[case::lone_cr("+++\r+S \r+++", "+++\n+X \n+++")]
+    #[case::trailing_blank_lines("+++\n+S \n+++\n\n\r\n", "+++\n+X \n+++")]
+    #[case::open_bottom_row("S+F\n   \n", "X+F\n   ")]
+    #[case::short_lines("+++\n+S\n+", "+++\n+X+\n+++")]
+    #[case::tabs("S\tF\n+", "X       F\n+++++++++")]
+    fn test_lenient(#[case] maze: &str, #[case] exp: &str) {
+        let act = TextMaze::parse(maze, ParseMode::Lenient, &Legend::default())
+            .expect("maze to create successfully");
+
+        assert_eq!(act.to_string(), exp)
+    }
+
+    #[rstest]
+    fn test_strict_reports_every_problem() {
+        let act = TextMaze::parse("S \n+\nSSS\n", ParseMode::Strict, &Legend::default())
+            .expect_err("maze to fail");
+        let line = |line, column| SourceLocation { line, column };
+
+        assert_eq!(
+            act,
+            MazeError::Multiple(vec![
+                MazeError::RaggedLine {
+                    at: line(2, 2),
+                    expected_width: 2
+                },
+                MazeError::MultipleStarts {
+                    first: line(1, 1),
+                    second: line(3, 1)
+                },
+                MazeError::MultipleStarts {
+                    first: line(1, 1),
+                    second: line(3, 2)
+                },
+                MazeError::RaggedLine {
+                    at: line(3, 3),
+                    expected_width: 2
+                },
+            ])
+        )
+    }
+
+    #[rstest]
+    fn test_legend() {
+        let legend = "#=wall .=open @=start E=finish"
+            .parse()
+            .expect("legend to parse");
+        let mut maze = TextMaze::parse("#####\n@.#.E\n#...#", ParseMode::Strict, &legend)
+            .expect("maze to create successfully");
+
+        assert_eq!(maze.look_dir(Direction::East), Cell::Open);
+        assert_eq!(maze.cell_at((4, 1)), Cell::Finish);
+        assert!(maze.move_dir(Direction::North).is_err());
+    }
+
+    #[rstest]
+    fn test_legend_header_wins_and_keeps_line_numbers() {
+        let act = TextMaze::parse(
+            "legend: #=wall @=start\n#@\n#",
+            ParseMode::Strict,
+            &Legend::default(),
+        );
+
+        match act {
+            Err(MazeError::RaggedLine { at, .. }) => assert_eq!(at.line, 3),
+            other => panic!("expected RaggedLine, got {other:?}"),
+        }
+    }
+
+    #[rstest]
+    fn test_finish_offsets_from_robot() {
+        let maze = TextMaze::try_from("F  \nS F").expect("maze to create successfully");
+        assert_eq!(maze.finish_offsets(), None);
+
+        let mut maze = maze.with_known_finish();
+        maze.move_dir(Direction::East).expect("move to succeed");
+
+        assert_eq!(maze.finish_offsets(), Some(vec![(-1, 1), (1, 0)]));
+    }
 }
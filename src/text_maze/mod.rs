@@ -1,7 +1,8 @@
-mod cell_impl;
+mod grid;
+mod legend;
 mod maze_impl;
 mod robot_impl;
 
-pub use cell_impl::TextCell;
-pub use maze_impl::TextMaze;
+pub use legend::{Legend, Role};
+pub use maze_impl::{ParseMode, TextMaze};
 pub use robot_impl::TextRobot;
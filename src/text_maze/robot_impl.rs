@@ -1,19 +1,26 @@
 use std::fmt::Display;
 
-use maze_robot::controller::{MazeError, Robot, RobotInternal};
+use maze_robot::controller::{Maze, MazeError, Robot, RobotInternal};
 
 use crate::text_maze::TextMaze;
 
 #[derive(Debug)]
 pub struct TextRobot(RobotInternal);
 
+impl TextRobot {
+    /// Place a robot in any maze built on top of a `TextMaze`, such as one wrapped for animation.
+    pub fn new<M: 'static + Maze>(maze: M) -> Self {
+        Self(RobotInternal::new(maze))
+    }
+}
+
 impl TryFrom<&str> for TextRobot {
     type Error = MazeError;
 
     fn try_from(value: &str) -> Result<Self, Self::Error> {
         let maze = TextMaze::try_from(value)?;
 
-        Ok(TextRobot(RobotInternal::new(maze)))
+        Ok(TextRobot::new(maze))
     }
 }
 